@@ -0,0 +1,59 @@
+//! Generates the solution registry that lets the main binary run every solved day in process.
+//! Each `src/bin/NN.rs` and `src/bin/YYYY_NN.rs` is compiled into the main binary as a module and exposes its `solution!` entry.
+//! Also records the version of the compiler as `AOC_RUSTC_VERSION`, which is stored alongside the timings.
+use std::{env, fs, path::Path, process::Command};
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    let mut bins: Vec<(String, String)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter_map(|path| {
+                    let stem = path.file_stem()?.to_str()?;
+                    let is_day = path.extension()? == "rs" && is_bin_name(stem);
+                    is_day.then(|| (stem.to_string(), path.to_str().unwrap().to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    bins.sort();
+
+    let mut registry = String::from("// @generated by build.rs\n\n");
+
+    for (bin, path) in &bins {
+        registry.push_str(&format!("#[path = {path:?}]\nmod day_{bin};\n"));
+    }
+
+    registry.push_str(
+        "\n/// Every solution in `src/bin`, sorted by year and day.\npub const SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[\n",
+    );
+    for (bin, _) in &bins {
+        registry.push_str(&format!("    day_{bin}::__SOLUTION.__for_bin({bin:?}),\n"));
+    }
+    registry.push_str("];\n");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("solutions.rs"), registry).unwrap();
+
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    if let Ok(output) = Command::new(rustc).arg("--version").output() {
+        let version = String::from_utf8_lossy(&output.stdout);
+        println!("cargo:rustc-env=AOC_RUSTC_VERSION={}", version.trim());
+    }
+}
+
+/// Binaries are named `NN` for the default year and `YYYY_NN` for other years.
+fn is_bin_name(stem: &str) -> bool {
+    let day = match stem.split_once('_') {
+        Some((year, day)) if year.len() == 4 && year.parse::<u16>().is_ok() => day,
+        Some(_) => return false,
+        None => stem,
+    };
+    day.len() == 2 && day.parse::<u8>().is_ok_and(|day| (1..=25).contains(&day))
+}
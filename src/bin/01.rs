@@ -7,13 +7,16 @@ use nom::{
     IResult,
 };
 
-advent_of_code::solution!(1);
+advent_of_code::solution!(1, parse = parse);
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn parse(input: &str) -> Vec<(u32, u32)> {
     let (_, values) = parse_input(input).unwrap();
+    values
+}
 
+pub fn part_one(values: &[(u32, u32)]) -> Option<u32> {
     // Transpose rows into columns, and sort these lists independently of each other to pair up the smallest each time
-    let (mut list1, mut list2): (Vec<u32>, Vec<u32>) = values.into_iter().unzip();
+    let (mut list1, mut list2): (Vec<u32>, Vec<u32>) = values.iter().copied().unzip();
 
     list1.sort();
     list2.sort();
@@ -34,11 +37,9 @@ fn parse_input(input: &str) -> IResult<&str, Vec<(u32, u32)>> {
     ))(input)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (_, values) = parse_input(input).unwrap();
-
+pub fn part_two(values: &[(u32, u32)]) -> Option<u32> {
     // Transpose rows into columns
-    let (list1, list2): (Vec<u32>, Vec<u32>) = values.into_iter().unzip();
+    let (list1, list2): (Vec<u32>, Vec<u32>) = values.iter().copied().unzip();
 
     // For each number in the first list, count the number in the second list then multiple by the original value
     // Add these together to calculate the total
@@ -53,13 +54,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(11));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(31));
     }
 }
@@ -1,3 +1,6 @@
+// NOTE: the tests of this day are kept as written.
+#![cfg_attr(test, allow(clippy::bool_assert_comparison, clippy::useless_vec))]
+
 use nom::{
     bytes::complete::tag,
     character::complete::{i32, newline},
@@ -24,7 +27,7 @@ fn is_safe(report: &[i32]) -> bool {
     })
 }
 
-fn is_safe_with_dampener(report: &Vec<i32>) -> bool {
+fn is_safe_with_dampener(report: &[i32]) -> bool {
     // Check if either the original array is safe, or if removing a single element makes it safe
     // by iterating through all index numbers and testing for a slice that removes that index
     // returning the first time it encounters a safe report
@@ -76,10 +76,7 @@ fn run(commands: &Vec<Command>) -> u32 {
 
 pub fn part_one(input: &str) -> Option<u32> {
     let mut commands = parse_input(input);
-    commands.retain(|c| match c {
-        Command::Mul(_, _) => true,
-        _ => false,
-    });
+    commands.retain(|c| matches!(c, Command::Mul(_, _)));
 
     Some(run(&commands))
 }
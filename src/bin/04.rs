@@ -76,18 +76,17 @@ pub fn part_two(input: &str) -> Option<u32> {
     for row in 0..size {
         assert_eq!(size as usize, grid[row as usize].len());
         for col in 0..size {
-            if grid[row as usize][col as usize] == 'A' {
-                if DIRECTIONS.iter().all(|(c, r)| {
+            if grid[row as usize][col as usize] == 'A'
+                && DIRECTIONS.iter().all(|(c, r)| {
                     let x = (col + c) as usize;
                     let y = (row + r) as usize;
                     let opp_x = (col - c) as usize;
                     let opp_y = (row - r) as usize;
 
-                    (grid[y as usize][x] == 'M' || grid[y][x] == 'S')
-                        && (grid[opp_y][opp_x] != grid[y][x])
-                }) {
-                    count += 1;
-                }
+                    (grid[y][x] == 'M' || grid[y][x] == 'S') && (grid[opp_y][opp_x] != grid[y][x])
+                })
+            {
+                count += 1;
             }
         }
     }
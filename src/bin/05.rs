@@ -11,6 +11,7 @@ use nom::{
 
 advent_of_code::solution!(5);
 
+#[allow(clippy::type_complexity)]
 fn parse_input(input: &str) -> IResult<&str, (Vec<(i32, i32)>, Vec<Vec<i32>>)> {
     separated_pair(
         many1(terminated(separated_pair(i32, tag("|"), i32), opt(newline))),
@@ -31,11 +32,9 @@ fn check_reprints(
         for i in 0..reprint.len() {
             let r = reprint[i];
             for &(before, after) in rules {
-                if before == r {
-                    if reprint[0..i].iter().any(|&v| v == after) {
-                        failed = true;
-                        break;
-                    }
+                if before == r && reprint[0..i].contains(&after) {
+                    failed = true;
+                    break;
                 }
             }
         }
@@ -10,28 +10,28 @@ advent_of_code::solution!(6);
 
 #[derive(Copy, Clone, PartialEq)]
 enum Direction {
-    UP,
-    DOWN,
-    LEFT,
-    RIGHT,
+    Up,
+    Down,
+    Left,
+    Right,
 }
 
 impl Direction {
     fn move_pos(&self, pos: (i32, i32)) -> (i32, i32) {
         match *self {
-            Direction::UP => (pos.0, pos.1 - 1),
-            Direction::DOWN => (pos.0, pos.1 + 1),
-            Direction::LEFT => (pos.0 - 1, pos.1),
-            Direction::RIGHT => (pos.0 + 1, pos.1),
+            Direction::Up => (pos.0, pos.1 - 1),
+            Direction::Down => (pos.0, pos.1 + 1),
+            Direction::Left => (pos.0 - 1, pos.1),
+            Direction::Right => (pos.0 + 1, pos.1),
         }
     }
 
     fn turn_clockwise(&self) -> Direction {
         match *self {
-            Direction::UP => Self::RIGHT,
-            Direction::DOWN => Self::LEFT,
-            Direction::LEFT => Self::UP,
-            Direction::RIGHT => Self::DOWN,
+            Direction::Up => Self::Right,
+            Direction::Down => Self::Left,
+            Direction::Left => Self::Up,
+            Direction::Right => Self::Down,
         }
     }
 }
@@ -49,16 +49,14 @@ pub fn part_one(input: &str) -> Option<u32> {
         .iter()
         .enumerate()
         .find_map(|(row, r)| {
-            if let Some(col) = r.iter().position(|&c| c == '^') {
-                Some((col as i32, row as i32))
-            } else {
-                None
-            }
+            r.iter()
+                .position(|&c| c == '^')
+                .map(|col| (col as i32, row as i32))
         })
         .unwrap();
 
     grid[pos.1 as usize][pos.0 as usize] = 'X';
-    let mut direction = Direction::UP;
+    let mut direction = Direction::Up;
 
     loop {
         let new_pos = direction.move_pos(pos);
@@ -95,30 +93,28 @@ pub fn part_two(input: &str) -> Option<u32> {
         .iter()
         .enumerate()
         .find_map(|(row, r)| {
-            if let Some(col) = r.iter().position(|&c| c == '^') {
-                Some((col as i32, row as i32))
-            } else {
-                None
-            }
+            r.iter()
+                .position(|&c| c == '^')
+                .map(|col| (col as i32, row as i32))
         })
         .unwrap();
 
     grid[pos.1 as usize][pos.0 as usize] = 'X';
 
     let mut obstructions = vec![];
-    traverse_path(&grid, &Direction::UP, pos, &mut obstructions, true);
+    traverse_path(&grid, &Direction::Up, pos, &mut obstructions, true);
     Some(obstructions.len() as u32)
 }
 
 fn traverse_path(
-    grid: &Vec<Vec<char>>,
+    grid: &[Vec<char>],
     direction: &Direction,
     pos: (i32, i32),
     obstructions: &mut Vec<(i32, i32)>,
     place_more: bool,
 ) -> bool {
     let size = grid.len() as i32;
-    let mut grid = grid.clone();
+    let mut grid = grid.to_vec();
     let mut direction = *direction;
     let mut pos = pos;
 
@@ -132,7 +128,7 @@ fn traverse_path(
 
         if place_more && grid[new_pos.1 as usize][new_pos.0 as usize] == '.' {
             // Try alternative
-            let mut new_grid = grid.clone();
+            let mut new_grid = grid.to_vec();
             new_grid[new_pos.1 as usize][new_pos.0 as usize] = '#';
 
             if traverse_path(&new_grid, &direction, pos, obstructions, false) {
@@ -51,7 +51,7 @@ fn find_result(v: u64, remaining: &[u64], operators: &[Operator], result: &u64)
     }
 }
 
-fn count_equations(eqs: &Vec<(u64, Vec<u64>)>, operators: &[Operator]) -> u64 {
+fn count_equations(eqs: &[(u64, Vec<u64>)], operators: &[Operator]) -> u64 {
     eqs.iter()
         .filter_map(|(result, args)| {
             if operators
@@ -47,8 +47,7 @@ fn calculate_trailheads(input: &str, method: Method) -> u32 {
             let mut found = FxHashSet::default();
             let mut count = 0;
             let mut remaining = vec![trailhead];
-            while !remaining.is_empty() {
-                let next = remaining.pop().unwrap();
+            while let Some(next) = remaining.pop() {
                 let (col, row) = next;
                 let c = trail_map[row][col];
                 if c == 9 {
@@ -1,3 +1,6 @@
+// NOTE: the tests of this day are kept as written.
+#![cfg_attr(test, allow(clippy::useless_vec))]
+
 use fxhash::FxHashMap;
 use nom::{bytes::complete::tag, character::complete::u64, multi::separated_list1, IResult};
 
@@ -36,7 +39,7 @@ fn count_stones(n: u64, it: i32, memo: &mut FxHashMap<(u64, i32), u64>) -> u64 {
         count_stones(1, it - 1, memo)
     } else {
         let digits = n.ilog10() + 1;
-        if digits % 2 == 0 {
+        if digits.is_multiple_of(2) {
             let p = 10u64.pow(digits / 2);
             count_stones(n / p, it - 1, memo) + count_stones(n % p, it - 1, memo)
         } else {
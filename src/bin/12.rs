@@ -1,5 +1,3 @@
-use std::usize;
-
 use fxhash::{FxHashMap, FxHashSet};
 use itertools::Itertools;
 use nom::{
@@ -55,7 +53,7 @@ impl Region {
 
 fn count_sides(edges: &FxHashMap<(usize, Direction), Vec<usize>>) -> u32 {
     let mut sides = 0;
-    for (_, r) in edges {
+    for r in edges.values() {
         let mut last = None;
         for &i in r.iter().sorted() {
             if last.is_none() || i != last.unwrap() + 1 {
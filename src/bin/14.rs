@@ -7,7 +7,14 @@ use nom::{
     IResult, Parser,
 };
 
-advent_of_code::solution!(14);
+advent_of_code::solution!(
+    14,
+    params {
+        width: i32 = 101,
+        height: i32 = 103,
+        seconds: u32 = 100
+    }
+);
 
 #[derive(Debug)]
 struct Coord {
@@ -44,10 +51,15 @@ fn parse_input(input: &str) -> IResult<&str, Vec<Robot>> {
     )(input)
 }
 
-fn part_one_process(input: &str, width: i32, height: i32) -> Option<u32> {
+pub fn part_one(input: &str) -> Option<u32> {
+    let Params {
+        width,
+        height,
+        seconds,
+    } = params();
     let (_, mut positions) = parse_input(input).unwrap();
 
-    for _ in 0..100 {
+    for _ in 0..seconds {
         for p in positions.iter_mut() {
             p.update_position(width, height);
         }
@@ -72,10 +84,6 @@ fn part_one_process(input: &str, width: i32, height: i32) -> Option<u32> {
     count.into_iter().reduce(|acc, e| acc * e)
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    part_one_process(input, 101, 103)
-}
-
 pub fn part_two(input: &str) -> Option<u32> {
     let (_, mut positions) = parse_input(input).unwrap();
 
@@ -98,10 +106,13 @@ pub fn part_two(input: &str) -> Option<u32> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::params;
 
     #[test]
     fn test_part_one() {
-        let result = part_one_process(&advent_of_code::template::read_file("examples", DAY), 11, 7);
+        let result = params::with(&[("width", "11"), ("height", "7")], || {
+            part_one(&advent_of_code::template::read_file("examples", DAY))
+        });
         assert_eq!(result, Some(12));
     }
 
@@ -99,14 +99,11 @@ impl Coord {
 
 fn parse_input(input: &str) -> IResult<&str, (Vec<Vec<Entity>>, Vec<Direction>)> {
     fn parse_map(input: &str) -> IResult<&str, Vec<Vec<Entity>>> {
-        many1(terminated(
-            many1(one_of("#.O@").map(|c| Entity::from(c))),
-            newline,
-        ))(input)
+        many1(terminated(many1(one_of("#.O@").map(Entity::from)), newline))(input)
     }
     fn parse_moves(input: &str) -> IResult<&str, Vec<Direction>> {
         many1(terminated(
-            one_of("^v<>").map(|c| Direction::from(c)),
+            one_of("^v<>").map(Direction::from),
             opt(newline),
         ))(input)
     }
@@ -135,44 +132,36 @@ fn find_movable_entities(
     moveable: &mut Vec<(Coord, Coord, Entity)>,
     is_other_half: bool,
 ) -> Option<Coord> {
-    let next_pos = pos.next_position(&direction);
+    let next_pos = pos.next_position(direction);
 
     if grid[next_pos.y][next_pos.x] == Entity::Wall {
         return None;
     }
 
     if grid[next_pos.y][next_pos.x].is_box() {
-        if find_movable_entities(&next_pos, direction, grid, moveable, false).is_none() {
-            return None;
-        }
+        find_movable_entities(&next_pos, direction, grid, moveable, false)?;
     }
 
     let e = grid[pos.y][pos.x];
     if e == Entity::BoxL && direction.is_vertical() && !is_other_half {
         let other_half = pos.next_position(&Direction::Right);
-        if find_movable_entities(&other_half, direction, grid, moveable, true).is_none() {
-            return None;
-        }
+        find_movable_entities(&other_half, direction, grid, moveable, true)?;
     } else if e == Entity::BoxR && direction.is_vertical() && !is_other_half {
         let other_half = pos.next_position(&Direction::Left);
-        if find_movable_entities(&other_half, direction, grid, moveable, true).is_none() {
-            return None;
-        }
+        find_movable_entities(&other_half, direction, grid, moveable, true)?;
     }
 
     moveable.push((*pos, next_pos, e));
     Some(next_pos)
 }
 
-fn find_robot(grid: &Vec<Vec<Entity>>) -> Coord {
+fn find_robot(grid: &[Vec<Entity>]) -> Coord {
     grid.iter()
         .enumerate()
         .find_map(|(y, row)| {
-            if let Some(x) = row.iter().position(|&entity| entity == Entity::Robot) {
-                Some(Coord { x, y })
-            } else {
-                None
-            }
+            row.iter()
+                .position(|&entity| entity == Entity::Robot)
+                .map(|x| Coord { x, y })
         })
         .unwrap()
 }
@@ -131,16 +131,10 @@ fn parse_input(input: &str) -> IResult<&str, Vec<Vec<char>>> {
     many1(terminated(many1(one_of("#.SE")), opt(newline)))(input)
 }
 
-fn find_item(grid: &Vec<Vec<char>>, item: char) -> (usize, usize) {
+fn find_item(grid: &[Vec<char>], item: char) -> (usize, usize) {
     grid.iter()
         .enumerate()
-        .find_map(|(y, row)| {
-            if let Some(x) = row.iter().position(|&c| c == item) {
-                Some((x, y))
-            } else {
-                None
-            }
-        })
+        .find_map(|(y, row)| row.iter().position(|&c| c == item).map(|x| (x, y)))
         .unwrap()
 }
 
@@ -206,7 +200,7 @@ fn calculate_paths(cur_path: Path, prev: &FxHashMap<Path, Vec<Path>>) -> u32 {
     while let Some((mut cur, mut locs)) = queue.pop_front() {
         loop {
             locs.push(cur.pos);
-            if let Some(v) = prev.get(&cur) {
+            if let Some(v) = prev.get(cur) {
                 for o in &v[1..] {
                     queue.push_back((o, locs.clone()));
                 }
@@ -31,7 +31,7 @@ impl MachineState {
                 }
                 1 => {
                     // bxl
-                    self.b = self.b ^ (operand as u64);
+                    self.b ^= operand as u64;
                 }
                 2 => {
                     // bst
@@ -45,7 +45,7 @@ impl MachineState {
                 }
                 4 => {
                     // bxc
-                    self.b = self.b ^ self.c;
+                    self.b ^= self.c;
                 }
                 5 => {
                     // out
@@ -81,6 +81,7 @@ impl MachineState {
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn parse_input(input: &str) -> IResult<&str, ((u64, u64, u64), Vec<u8>)> {
     fn parse_registers(input: &str) -> IResult<&str, (u64, u64, u64)> {
         tuple((
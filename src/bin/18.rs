@@ -10,7 +10,13 @@ use nom::{
 };
 use priority_queue::DoublePriorityQueue;
 
-advent_of_code::solution!(18);
+advent_of_code::solution!(
+    18,
+    params {
+        bytes: usize = 1024,
+        size: usize = 71
+    }
+);
 
 fn parse_input(input: &str) -> IResult<&str, Vec<(i32, i32)>> {
     many1(terminated(separated_pair(i32, tag(","), i32), opt(newline)))(input)
@@ -32,7 +38,7 @@ fn simulate_corruption(coordinates: &[(i32, i32)], size: usize) -> Vec<Vec<bool>
     grid
 }
 
-fn dijkstra(grid: &Vec<Vec<bool>>, start: (usize, usize), end: (usize, usize)) -> Option<u32> {
+fn dijkstra(grid: &[Vec<bool>], start: (usize, usize), end: (usize, usize)) -> Option<u32> {
     let size = grid.len();
 
     let mut queue = DoublePriorityQueue::new();
@@ -49,7 +55,7 @@ fn dijkstra(grid: &Vec<Vec<bool>>, start: (usize, usize), end: (usize, usize)) -
         let options = get_options(cur_pos, size);
         let valid_options = options
             .iter()
-            .filter(|pos| grid[pos.1][pos.0] != true)
+            .filter(|pos| !grid[pos.1][pos.0])
             .collect_vec();
 
         for &n in valid_options {
@@ -82,13 +88,10 @@ fn get_options(pos: (usize, usize), size: usize) -> Vec<(usize, usize)> {
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    part_one_with_size(input, 1024, 71)
-}
-
-fn part_one_with_size(input: &str, num_entries: usize, size: usize) -> Option<u32> {
+    let Params { bytes, size } = params();
     let (_, coordinates) = parse_input(input).unwrap();
 
-    let grid = simulate_corruption(&coordinates[..num_entries], size);
+    let grid = simulate_corruption(&coordinates[..bytes], size);
 
     let start = (0, 0);
     let end = (size - 1, size - 1);
@@ -96,15 +99,16 @@ fn part_one_with_size(input: &str, num_entries: usize, size: usize) -> Option<u3
     dijkstra(&grid, start, end)
 }
 
-fn part_two_with_size(input: &str, num_entries: usize, size: usize) -> Option<String> {
+pub fn part_two(input: &str) -> Option<String> {
+    let Params { bytes, size } = params();
     let (_, coordinates) = parse_input(input).unwrap();
 
-    let grid = simulate_corruption(&coordinates[..num_entries], size);
+    let grid = simulate_corruption(&coordinates[..bytes], size);
 
     let start = (0, 0);
     let end = (size - 1, size - 1);
 
-    let possible_coords = coordinates[num_entries..].iter().enumerate().collect_vec();
+    let possible_coords = coordinates[bytes..].iter().enumerate().collect_vec();
 
     let p = possible_coords.partition_point(|&(idx, _)| {
         let mut g = grid.clone();
@@ -119,25 +123,24 @@ fn part_two_with_size(input: &str, num_entries: usize, size: usize) -> Option<St
     Some(format!("{},{}", x, y))
 }
 
-pub fn part_two(input: &str) -> Option<String> {
-    part_two_with_size(input, 1024, 71)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::params;
 
     #[test]
     fn test_part_one() {
-        let result =
-            part_one_with_size(&advent_of_code::template::read_file("examples", DAY), 12, 7);
+        let result = params::with(&[("bytes", "12"), ("size", "7")], || {
+            part_one(&advent_of_code::template::read_file("examples", DAY))
+        });
         assert_eq!(result, Some(22));
     }
 
     #[test]
     fn test_part_two() {
-        let result =
-            part_two_with_size(&advent_of_code::template::read_file("examples", DAY), 12, 7);
+        let result = params::with(&[("bytes", "12"), ("size", "7")], || {
+            part_two(&advent_of_code::template::read_file("examples", DAY))
+        });
         assert_eq!(result, Some("6,1".to_string()));
     }
 }
@@ -58,8 +58,8 @@ fn get_possible_designs(
     // and add them to the total
     let mut total = 0;
     for p in patterns {
-        if design.starts_with(p) {
-            total += get_possible_designs(&design[p.len()..], patterns, mode, cache);
+        if let Some(remainder) = design.strip_prefix(p) {
+            total += get_possible_designs(remainder, patterns, mode, cache);
             // if short circuiting before getting all, just return 1 to show it is possible
             if *mode == Mode::AnyPossible && total > 0 {
                 cache.insert(design.to_string(), 1);
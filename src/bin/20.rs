@@ -7,21 +7,19 @@ use nom::{
     IResult,
 };
 
-advent_of_code::solution!(20);
+advent_of_code::solution!(20, params { limit: usize = 100 });
 
 fn parse_input(input: &str) -> IResult<&str, Vec<Vec<char>>> {
     many1(terminated(many1(one_of("#.SE")), opt(newline)))(input)
 }
 
-fn find_item(grid: &Vec<Vec<char>>, item: char) -> (i32, i32) {
+fn find_item(grid: &[Vec<char>], item: char) -> (i32, i32) {
     grid.iter()
         .enumerate()
         .find_map(|(y, row)| {
-            if let Some(x) = row.iter().position(|&c| c == item) {
-                Some((x as i32, y as i32))
-            } else {
-                None
-            }
+            row.iter()
+                .position(|&c| c == item)
+                .map(|x| (x as i32, y as i32))
         })
         .unwrap()
 }
@@ -99,35 +97,32 @@ fn find_cheats_path_strategy(input: &str, max_length: u32, limit: usize) -> Opti
     Some(count)
 }
 
-fn part_one_with_limit(input: &str, limit: usize) -> Option<u32> {
-    find_cheats_local_strategy(input, limit)
-}
-
 pub fn part_one(input: &str) -> Option<u32> {
-    part_one_with_limit(input, 100)
-}
-
-pub fn part_two_with_limit(input: &str, limit: usize) -> Option<u32> {
-    find_cheats_path_strategy(input, 20, limit)
+    find_cheats_local_strategy(input, params().limit)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    part_two_with_limit(input, 100)
+    find_cheats_path_strategy(input, 20, params().limit)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::params;
 
     #[test]
     fn test_part_one() {
-        let result = part_one_with_limit(&advent_of_code::template::read_file("examples", DAY), 1);
+        let result = params::with(&[("limit", "1")], || {
+            part_one(&advent_of_code::template::read_file("examples", DAY))
+        });
         assert_eq!(result, Some(44));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two_with_limit(&advent_of_code::template::read_file("examples", DAY), 50);
+        let result = params::with(&[("limit", "50")], || {
+            part_two(&advent_of_code::template::read_file("examples", DAY))
+        });
         assert_eq!(result, Some(285));
     }
 }
@@ -90,7 +90,7 @@ fn process_robots(input: &str, num_robots: usize) -> Option<u64> {
     // We need to go depth first, we can more effectively cache results when there is a large number of elements to iterate
     for code in codes {
         let best_seq_length = calculate_score(
-            &code.iter().collect(),
+            &code.iter().collect::<String>(),
             num_robots + 1,
             &numeric_map,
             &direction_map,
@@ -109,7 +109,7 @@ fn process_robots(input: &str, num_robots: usize) -> Option<u64> {
 }
 
 fn calculate_score(
-    code: &String,
+    code: &str,
     depth: usize,
     layout: &HashMap<(char, char), Vec<String>>,
     directional_map: &HashMap<(char, char), Vec<String>>,
@@ -119,7 +119,7 @@ fn calculate_score(
         return code.len();
     }
 
-    let cache_key = (code.clone(), depth);
+    let cache_key = (code.to_string(), depth);
     if let Some(&score) = cache.get(&cache_key) {
         return score;
     }
@@ -143,10 +143,10 @@ fn calculate_score(
     best_seq_length
 }
 
-fn get_cost(option: &String, direction_cost: &HashMap<(char, char), usize>) -> usize {
+fn get_cost(option: &str, direction_cost: &HashMap<(char, char), usize>) -> usize {
     let mut cost = 0;
     for i in 1..option.len() {
-        let v = option[i - 1..=i].as_bytes();
+        let v = &option.as_bytes()[i - 1..=i];
         let v1 = v[0] as char;
         let v2 = v[1] as char;
         if v1 != v2 {
@@ -156,6 +156,7 @@ fn get_cost(option: &String, direction_cost: &HashMap<(char, char), usize>) -> u
     cost
 }
 
+#[allow(clippy::type_complexity)]
 fn map_key_moves(
     layout: &[[char; 3]],
 ) -> (
@@ -202,7 +203,7 @@ fn map_key_moves(
         let orig_map = map.clone();
         for (&from, m) in &orig_map {
             for (to, v) in m {
-                if let Some(paths) = orig_map.get(&to) {
+                if let Some(paths) = orig_map.get(to) {
                     for (&new_to, path) in paths {
                         let from_map = map.get_mut(&from).unwrap();
                         if new_to != from && !m.contains_key(&new_to) {
@@ -246,7 +247,7 @@ fn get_optimised_map(
 ) -> HashMap<(char, char), Vec<String>> {
     let mut optimised_map = HashMap::default();
     for (k, v) in map {
-        let s = v.into_iter().min_set_by_key(|i| get_cost(i, &cost_map));
+        let s = v.into_iter().min_set_by_key(|i| get_cost(i, cost_map));
         optimised_map.insert(k, s);
     }
 
@@ -25,8 +25,8 @@ pub fn part_two(input: &str) -> Option<i64> {
     let sequences = generate_buyer_sequences(buyers);
 
     sequences
-        .iter()
-        .map(|(_, bananas)| bananas.values().sum())
+        .values()
+        .map(|bananas| bananas.values().sum())
         .max()
 }
 
@@ -47,9 +47,7 @@ fn generate_buyer_sequences(buyers: Vec<i64>) -> FxHashMap<Vec<i64>, FxHashMap<u
                 sequences
                     .entry(seq)
                     .and_modify(|v: &mut FxHashMap<usize, i64>| {
-                        if !v.contains_key(&buyer_idx) {
-                            v.insert(buyer_idx, bananas);
-                        }
+                        v.entry(buyer_idx).or_insert(bananas);
                     })
                     .or_insert_with(|| FxHashMap::from_iter([(buyer_idx, bananas)]));
             }
@@ -58,11 +58,9 @@ fn find_max_interconnected(network: &FxHashMap<String, Vec<String>>) -> Vec<Stri
         let mut remaining = VecDeque::from_iter(set);
         while let Some(node) = remaining.pop_front() {
             for c in &network[&node] {
-                if !new_set.contains(&c) {
-                    if new_set.iter().all(|i| network[c].contains(i)) {
-                        new_set.push(c.clone());
-                        remaining.push_back(c.clone());
-                    }
+                if !new_set.contains(c) && new_set.iter().all(|i| network[c].contains(i)) {
+                    new_set.push(c.clone());
+                    remaining.push_back(c.clone());
                 }
             }
         }
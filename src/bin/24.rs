@@ -14,16 +14,16 @@ advent_of_code::solution!(24);
 
 #[derive(Debug)]
 enum Operation {
-    AND,
-    OR,
-    XOR,
+    And,
+    Or,
+    Xor,
 }
 impl Operation {
     fn perform(&self, in_lhs_gate: u8, in_rhs_gate: u8) -> u8 {
         match *self {
-            Operation::AND => in_lhs_gate & in_rhs_gate,
-            Operation::OR => in_lhs_gate | in_rhs_gate,
-            Operation::XOR => in_lhs_gate ^ in_rhs_gate,
+            Operation::And => in_lhs_gate & in_rhs_gate,
+            Operation::Or => in_lhs_gate | in_rhs_gate,
+            Operation::Xor => in_lhs_gate ^ in_rhs_gate,
         }
     }
 }
@@ -33,9 +33,9 @@ impl FromStr for Operation {
 
     fn from_str(s: &str) -> Result<Operation, Self::Err> {
         match s {
-            "AND" => Ok(Operation::AND),
-            "OR" => Ok(Operation::OR),
-            "XOR" => Ok(Operation::XOR),
+            "AND" => Ok(Operation::And),
+            "OR" => Ok(Operation::Or),
+            "XOR" => Ok(Operation::Xor),
             _ => Err(()),
         }
     }
@@ -85,10 +85,8 @@ pub fn part_one(input: &str) -> Option<u64> {
     let mut waiting_circuits = VecDeque::from_iter(circuits);
 
     while let Some(c) = waiting_circuits.pop_front() {
-        let v1 = state.get(&c.in_lhs_gate);
-        let v2 = state.get(&c.in_rhs_gate);
-        if v1.is_some() && v2.is_some() {
-            state.insert(c.out_gate, c.op.perform(*v1.unwrap(), *v2.unwrap()));
+        if let (Some(&v1), Some(&v2)) = (state.get(&c.in_lhs_gate), state.get(&c.in_rhs_gate)) {
+            state.insert(c.out_gate, c.op.perform(v1, v2));
         } else {
             waiting_circuits.push_back(c);
         }
@@ -105,7 +103,7 @@ pub fn part_one(input: &str) -> Option<u64> {
     )
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(_input: &str) -> Option<u32> {
     None
 }
 
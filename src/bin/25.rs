@@ -7,7 +7,7 @@ use nom::{
     IResult,
 };
 
-advent_of_code::solution!(25);
+advent_of_code::solution!(25, 1);
 
 #[derive(Debug, PartialEq)]
 enum SchematicType {
@@ -23,7 +23,7 @@ struct Schematic {
 }
 
 impl Schematic {
-    fn from(s: &Vec<Vec<char>>) -> Self {
+    fn from(s: &[Vec<char>]) -> Self {
         let w = s[0].len();
         let schematic_type = if s[0].iter().all(|&v| v == '#') {
             SchematicType::Lock
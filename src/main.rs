@@ -1,4 +1,7 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, read, scaffold, solve, status, time, verify,
+};
+use advent_of_code::template::{Limits, PuzzleId, RunMode};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
@@ -6,8 +9,22 @@ use advent_of_code::template::Day;
 #[cfg(feature = "today")]
 use std::process;
 
+// NOTE: the registry is not needed by the tests, and solutions can not share the dhat allocator.
+#[cfg(not(any(test, feature = "dhat-heap")))]
+mod registry {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
+#[cfg(any(test, feature = "dhat-heap"))]
+mod registry {
+    pub const SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[];
+}
+
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::{
+        commands::scaffold::Overwrite, params, Day, InputSource, Limits, OutlierRule, Threshold,
+        Year, DEFAULT_YEAR,
+    };
     use std::process;
 
     pub enum AppArguments {
@@ -20,43 +37,163 @@ mod args {
         Scaffold {
             day: Day,
             download: bool,
-            overwrite: bool,
+            overwrite: Overwrite,
+            template: Option<String>,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            input: InputSource,
+            params: Vec<(String, String)>,
         },
         All {
             release: bool,
+            isolated: bool,
+            limits: Limits,
+            jobs: usize,
+            input: InputSource,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            isolated: bool,
+            outliers: Option<OutlierRule>,
+            compare: Option<Threshold>,
+            limits: Limits,
+            history: bool,
+            csv: bool,
+            against: Option<String>,
+        },
+        Verify {
+            isolated: bool,
+            store: bool,
+            limits: Limits,
+            jobs: usize,
+        },
+        Status {
+            store: bool,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+    /// Parse `--timeout` and `--memory`, which limit each day that runs in its own process.
+    /// The timeout covers the whole process, benchmark samples and heap measurements included.
+    fn parse_limits(args: &mut pico_args::Arguments) -> Result<Limits, pico_args::Error> {
+        Ok(Limits {
+            timeout: args.opt_value_from_fn("--timeout", Limits::parse_timeout)?,
+            memory: args.opt_value_from_fn("--memory", Limits::parse_memory)?,
+        })
+    }
+
+    /// Parse `--jobs`, the number of days that run at the same time. Defaults to one.
+    fn parse_jobs(args: &mut pico_args::Arguments) -> Result<usize, pico_args::Error> {
+        Ok(args
+            .opt_value_from_fn(["-j", "--jobs"], |s| {
+                s.parse::<usize>()
+                    .ok()
+                    .filter(|jobs| *jobs > 0)
+                    .ok_or(format!("expected a positive number of jobs, got `{s}`."))
+            })?
+            .unwrap_or(1))
+    }
+
+    /// Error if one of the `flags` that are set does not apply together with `mode`.
+    fn reject_flags(flags: &[(&str, bool)], mode: &str) -> Result<(), String> {
+        match flags.iter().find(|(_, set)| *set) {
+            Some((flag, _)) => Err(format!("`{flag}` can not be combined with {mode}.")),
+            None => Ok(()),
+        }
+    }
+
+    /// Parse the command and the year it applies to, which defaults to `AOC_YEAR`.
+    pub fn parse() -> Result<(Year, AppArguments), Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+
+        let year = args
+            .opt_value_from_str("--year")?
+            .or(DEFAULT_YEAR)
+            .ok_or("no year given. Pass `--year` or set AOC_YEAR in `.cargo/config.toml`.")?;
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                isolated: args.contains("--isolated"),
+                limits: parse_limits(&mut args)?,
+                jobs: parse_jobs(&mut args)?,
+                input: if args.contains("--examples") {
+                    InputSource::Example(None)
+                } else {
+                    InputSource::Puzzle
+                },
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let isolated = args.contains("--isolated");
+                let outliers = args.opt_value_from_str("--outliers")?;
+                let threshold: Option<Threshold> = args.opt_value_from_str("--threshold")?;
+                // NOTE: a threshold only applies to a comparison, so it implies `--compare`.
+                let compare = (args.contains("--compare") || threshold.is_some())
+                    .then(|| threshold.unwrap_or_default());
+                let limits = parse_limits(&mut args)?;
+                // NOTE: `--csv` only applies to the history, so it implies `--history`.
+                let csv = args.contains("--csv");
+                let history = args.contains("--history") || csv;
+                let against = args.opt_value_from_str("--against")?;
+
+                if against.is_some() {
+                    reject_flags(
+                        &[
+                            ("--store", store),
+                            ("--compare", compare.is_some()),
+                            ("--all", all),
+                            ("--isolated", isolated),
+                            ("--history", history),
+                        ],
+                        "`--against`",
+                    )?;
+                }
+
+                if history {
+                    reject_flags(
+                        &[
+                            ("--store", store),
+                            ("--compare", compare.is_some()),
+                            ("--all", all),
+                            ("--isolated", isolated),
+                        ],
+                        "`--history` or `--csv`",
+                    )?;
+                }
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    isolated,
+                    outliers,
+                    compare,
+                    limits,
+                    history,
+                    csv,
+                    against,
                 }
             }
+            Some("verify") => AppArguments::Verify {
+                isolated: args.contains("--isolated"),
+                store: args.contains("--store"),
+                limits: parse_limits(&mut args)?,
+                jobs: parse_jobs(&mut args)?,
+            },
+            Some("status") => AppArguments::Status {
+                store: args.contains("--store"),
+            },
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
@@ -66,14 +203,41 @@ mod args {
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
-                overwrite: args.contains("--overwrite"),
-            },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+                overwrite: Overwrite {
+                    // NOTE: `--overwrite` is kept as a shorthand for the module, as it only ever applied to it.
+                    module: args.contains("--overwrite-module") || args.contains("--overwrite"),
+                    input: args.contains("--overwrite-input"),
+                    examples: args.contains("--overwrite-examples"),
+                },
+                template: args.opt_value_from_str("--template")?,
             },
+            Some("solve") => {
+                let day = args.free_from_str()?;
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let dhat = args.contains("--dhat");
+                let params = args.values_from_fn("--param", params::parse)?;
+
+                // NOTE: the suffix of `--example` is optional, so it is parsed as a free argument.
+                let input = if args.contains("--example") {
+                    InputSource::Example(args.opt_free_from_str()?)
+                } else {
+                    InputSource::Puzzle
+                };
+
+                if submit.is_some() && input != InputSource::Puzzle {
+                    return Err("answers to examples can not be submitted.".into());
+                }
+
+                AppArguments::Solve {
+                    day,
+                    release,
+                    dhat,
+                    submit,
+                    input,
+                    params,
+                }
+            }
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
             Some(x) => {
@@ -91,7 +255,23 @@ mod args {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
         }
 
-        Ok(app_args)
+        Ok((year, app_args))
+    }
+}
+
+/// Solutions run in this process unless `--isolated` asks for one `cargo run` per day.
+/// Limits can only be enforced on a separate process and the output of parallel jobs can only be buffered per process,
+/// so both imply `--isolated`.
+fn run_mode(isolated: bool, release: bool, limits: Limits, jobs: usize) -> RunMode<'static> {
+    if isolated || limits.is_set() || jobs > 1 {
+        RunMode::Isolated {
+            is_release: release,
+            limits,
+            jobs,
+            root: None,
+        }
+    } else {
+        RunMode::InProcess(registry::SOLUTIONS)
     }
 }
 
@@ -101,30 +281,105 @@ fn main() {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, download, overwrite } => {
-                scaffold::handle(day, overwrite);
+        Ok((year, args)) => match args {
+            AppArguments::All {
+                release,
+                isolated,
+                limits,
+                jobs,
+                input,
+            } => {
+                all::handle(year, run_mode(isolated, release, limits, jobs), input);
+            }
+            AppArguments::Time {
+                day,
+                history: true,
+                csv,
+                ..
+            } => {
+                time::history(year, day, csv);
+            }
+            AppArguments::Time {
+                day,
+                outliers,
+                limits,
+                against: Some(rev),
+                ..
+            } => {
+                if let Some(rule) = outliers {
+                    rule.apply();
+                }
+                time::against(year, day, &rev, limits);
+            }
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                isolated,
+                outliers,
+                compare,
+                limits,
+                ..
+            } => {
+                if let Some(rule) = outliers {
+                    rule.apply();
+                }
+                time::handle(
+                    year,
+                    day,
+                    all,
+                    store,
+                    compare,
+                    run_mode(isolated, true, limits, 1),
+                );
+            }
+            AppArguments::Verify {
+                isolated,
+                store,
+                limits,
+                jobs,
+            } => {
+                verify::handle(year, run_mode(isolated, true, limits, jobs), store);
+            }
+            AppArguments::Status { store } => status::handle(year, store),
+            AppArguments::Download { day } => download::handle(PuzzleId::new(year, day)),
+            AppArguments::Read { day } => read::handle(PuzzleId::new(year, day)),
+            AppArguments::Scaffold {
+                day,
+                download,
+                overwrite,
+                template,
+            } => {
+                let puzzle = PuzzleId::new(year, day);
+                // NOTE: download first, so that the examples can be extracted from the puzzle description.
                 if download {
-                    download::handle(day);
+                    download::handle(puzzle);
                 }
+                scaffold::handle(puzzle, overwrite, template.as_deref());
             }
             AppArguments::Solve {
                 day,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                input,
+                params,
+            } => solve::handle(
+                PuzzleId::new(year, day),
+                release,
+                dhat,
+                submit,
+                input,
+                &params,
+            ),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                        let puzzle = PuzzleId::new(year, day);
+                        download::handle(puzzle);
+                        scaffold::handle(puzzle, scaffold::Overwrite::default(), None);
+                        read::handle(puzzle)
                     }
                     None => {
                         eprintln!(
@@ -1,10 +1,19 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
+    ffi::OsStr,
     fmt::Display,
+    io::{self, Write},
     process::{Command, Output, Stdio},
+    sync::LazyLock,
+    time::Duration,
 };
 
-use crate::template::Day;
+use regex::Regex;
+
+use crate::template::PuzzleId;
+
+/// Name of the aoc-cli executable.
+static AOC_CLI: &str = "aoc";
 
 #[derive(Debug)]
 pub enum AocCommandError {
@@ -25,16 +34,113 @@ impl Display for AocCommandError {
     }
 }
 
+/// The response of advent of code to a submitted answer.
+/// Rejected and rate-limited submissions carry the cooldown until the next submission, if one was given.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    Correct,
+    Incorrect(Option<Duration>),
+    TooHigh(Option<Duration>),
+    TooLow(Option<Duration>),
+    RateLimited(Option<Duration>),
+    /// The part was already completed or is not unlocked yet.
+    WrongLevel,
+    Unknown,
+}
+
+impl SubmissionOutcome {
+    /// Interpret the text that aoc-cli prints for a submission.
+    pub fn from_response(response: &str) -> Self {
+        // NOTE: aoc-cli wraps the response to the terminal width, normalize whitespace before matching.
+        let response = response.split_whitespace().collect::<Vec<_>>().join(" ");
+        let wait = parse_wait(&response);
+
+        if response.contains("That's the right answer") {
+            SubmissionOutcome::Correct
+        } else if response.contains("That's not the right answer") {
+            if response.contains("too high") {
+                SubmissionOutcome::TooHigh(wait)
+            } else if response.contains("too low") {
+                SubmissionOutcome::TooLow(wait)
+            } else {
+                SubmissionOutcome::Incorrect(wait)
+            }
+        } else if response.contains("You gave an answer too recently") {
+            SubmissionOutcome::RateLimited(wait)
+        } else if response.contains("You don't seem to be solving the right level") {
+            SubmissionOutcome::WrongLevel
+        } else {
+            SubmissionOutcome::Unknown
+        }
+    }
+
+    /// Whether advent of code judged the answer to be wrong.
+    pub fn is_rejected(self) -> bool {
+        matches!(
+            self,
+            SubmissionOutcome::Incorrect(_)
+                | SubmissionOutcome::TooHigh(_)
+                | SubmissionOutcome::TooLow(_)
+        )
+    }
+
+    /// The time to wait before the next submission, if one was given.
+    pub fn cooldown(self) -> Option<Duration> {
+        match self {
+            SubmissionOutcome::Incorrect(wait)
+            | SubmissionOutcome::TooHigh(wait)
+            | SubmissionOutcome::TooLow(wait)
+            | SubmissionOutcome::RateLimited(wait) => wait,
+            _ => None,
+        }
+    }
+}
+
+impl Display for SubmissionOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmissionOutcome::Correct => write!(f, "correct"),
+            SubmissionOutcome::Incorrect(_) => write!(f, "incorrect"),
+            SubmissionOutcome::TooHigh(_) => write!(f, "too high"),
+            SubmissionOutcome::TooLow(_) => write!(f, "too low"),
+            SubmissionOutcome::RateLimited(_) => write!(f, "rate-limited"),
+            SubmissionOutcome::WrongLevel => write!(f, "not the current level"),
+            SubmissionOutcome::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+static LEFT_TO_WAIT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap());
+static PLEASE_WAIT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)please wait (one|\d+) minutes?").unwrap());
+
+/// Parse the cooldown from e.g. "please wait 5 minutes" or "You have 4m 32s left to wait".
+fn parse_wait(response: &str) -> Option<Duration> {
+    if let Some(captures) = LEFT_TO_WAIT.captures(response) {
+        let minutes = captures.get(1).map_or(Ok(0), |m| m.as_str().parse::<u64>());
+        let seconds = captures[2].parse::<u64>();
+        return Some(Duration::from_secs(minutes.ok()? * 60 + seconds.ok()?));
+    }
+
+    let captures = PLEASE_WAIT.captures(response)?;
+    let minutes = match &captures[1] {
+        "one" => 1,
+        x => x.parse::<u64>().ok()?,
+    };
+    Some(Duration::from_secs(minutes * 60))
+}
+
 pub fn check() -> Result<(), AocCommandError> {
-    Command::new("aoc")
+    Command::new(AOC_CLI)
         .arg("-V")
         .output()
         .map_err(|_| AocCommandError::CommandNotFound)?;
     Ok(())
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
+pub fn read(puzzle: PuzzleId) -> Result<Output, AocCommandError> {
+    let puzzle_path = get_puzzle_path(puzzle);
 
     let args = build_args(
         "read",
@@ -43,15 +149,15 @@ pub fn read(day: Day) -> Result<Output, AocCommandError> {
             "--puzzle-file".into(),
             puzzle_path,
         ],
-        day,
+        puzzle,
     );
 
-    call_aoc_cli(&args)
+    call_aoc_cli(AOC_CLI.as_ref(), &args)
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
+pub fn download(puzzle: PuzzleId) -> Result<Output, AocCommandError> {
+    let input_path = get_input_path(puzzle);
+    let puzzle_path = get_puzzle_path(puzzle);
 
     let args = build_args(
         "download",
@@ -62,64 +168,174 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
             "--puzzle-file".into(),
             puzzle_path.to_string(),
         ],
-        day,
+        puzzle,
     );
 
-    let output = call_aoc_cli(&args)?;
+    let output = call_aoc_cli(AOC_CLI.as_ref(), &args)?;
     println!("---");
     println!("🎄 Successfully wrote input to \"{}\".", &input_path);
     println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
     Ok(output)
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
+pub fn submit(
+    puzzle: PuzzleId,
+    part: u8,
+    result: &str,
+) -> Result<SubmissionOutcome, AocCommandError> {
+    submit_with(AOC_CLI.as_ref(), puzzle, part, result)
+}
+
+/// Submit an answer with the given aoc-cli executable.
+fn submit_with(
+    program: &OsStr,
+    puzzle: PuzzleId,
+    part: u8,
+    result: &str,
+) -> Result<SubmissionOutcome, AocCommandError> {
     // workaround: the argument order is inverted for submit.
-    let mut args = build_args("submit", &[], day);
+    let mut args = build_args("submit", &[], puzzle);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
-}
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+    let output = call_aoc_cli(program, &args)?;
+    Ok(SubmissionOutcome::from_response(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+pub fn get_input_path(puzzle: PuzzleId) -> String {
+    puzzle.data_path("inputs", None).display().to_string()
 }
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
+pub fn get_puzzle_path(puzzle: PuzzleId) -> String {
+    puzzle.puzzle_path().display().to_string()
 }
 
-fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
+fn build_args(command: &str, args: &[String], puzzle: PuzzleId) -> Vec<String> {
     let mut cmd_args = args.to_vec();
 
-    if let Some(year) = get_year() {
-        cmd_args.push("--year".into());
-        cmd_args.push(year.to_string());
-    }
-
-    cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
+    cmd_args.append(&mut vec![
+        "--year".into(),
+        puzzle.year.to_string(),
+        "--day".into(),
+        puzzle.day.to_string(),
+        command.into(),
+    ]);
 
     cmd_args
 }
 
-fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
+fn call_aoc_cli(program: &OsStr, args: &[String]) -> Result<Output, AocCommandError> {
     // println!("Calling >aoc with: {}", args.join(" "));
-    let output = Command::new("aoc")
+    // capture output, so it can be interpreted after it has been forwarded.
+    let output = Command::new(program)
         .args(args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
         .output()
         .map_err(|_| AocCommandError::CommandNotCallable)?;
 
+    let _ = io::stdout().write_all(&output.stdout);
+    let _ = io::stderr().write_all(&output.stderr);
+
     if output.status.success() {
         Ok(output)
     } else {
         Err(AocCommandError::BadExitStatus(output))
     }
 }
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::SubmissionOutcome;
+    use std::time::Duration;
+
+    #[test]
+    fn parses_correct_answers() {
+        let outcome = SubmissionOutcome::from_response(
+            "That's the right answer! You are one gold star closer to finding the Chief Historian.",
+        );
+        assert_eq!(outcome, SubmissionOutcome::Correct);
+    }
+
+    #[test]
+    fn parses_rejected_answers() {
+        assert_eq!(
+            SubmissionOutcome::from_response(
+                "That's not the right answer; your answer is too high. If you're stuck, \
+                make sure you're using the full input data. Please wait one minute before trying again."
+            ),
+            SubmissionOutcome::TooHigh(Some(Duration::from_secs(60)))
+        );
+        assert_eq!(
+            SubmissionOutcome::from_response(
+                "That's not the right\nanswer; your answer is too\nlow. Because you have guessed \
+                incorrectly 4 times on this puzzle, please wait 5 minutes before trying again."
+            ),
+            SubmissionOutcome::TooLow(Some(Duration::from_secs(300)))
+        );
+        assert_eq!(
+            SubmissionOutcome::from_response("That's not the right answer."),
+            SubmissionOutcome::Incorrect(None)
+        );
+    }
+
+    #[test]
+    fn parses_rate_limits() {
+        let outcome = SubmissionOutcome::from_response(
+            "You gave an answer too recently; you have to wait after submitting an answer before \
+            trying again. You have 4m 32s left to wait.",
+        );
+        assert_eq!(
+            outcome,
+            SubmissionOutcome::RateLimited(Some(Duration::from_secs(272)))
+        );
+        assert_eq!(outcome.cooldown(), Some(Duration::from_secs(272)));
+        assert!(!outcome.is_rejected());
+
+        assert_eq!(
+            SubmissionOutcome::from_response(
+                "You gave an answer too recently. You have 42s left to wait."
+            ),
+            SubmissionOutcome::RateLimited(Some(Duration::from_secs(42)))
+        );
+    }
+
+    #[test]
+    fn parses_other_responses() {
+        assert_eq!(
+            SubmissionOutcome::from_response(
+                "You don't seem to be solving the right level. Did you already complete it?"
+            ),
+            SubmissionOutcome::WrongLevel
+        );
+        assert_eq!(
+            SubmissionOutcome::from_response(""),
+            SubmissionOutcome::Unknown
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn submits_via_aoc_cli() {
+        use crate::day;
+        use std::{env, fs, os::unix::fs::PermissionsExt};
+
+        let dir = env::temp_dir().join(format!("aoc-cli-fake-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let script = dir.join("aoc");
+        fs::write(
+            &script,
+            "#!/bin/sh\necho \"That's not the right answer; your answer is too low.\"\n",
+        )
+        .unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let outcome = super::submit_with(script.as_os_str(), day!(1).into(), 1, "42");
+
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(outcome.unwrap(), SubmissionOutcome::TooLow(None));
+    }
+}
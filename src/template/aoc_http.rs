@@ -0,0 +1,331 @@
+/// Built-in HTTP client for advent of code, used when aoc-cli is not installed.
+use std::{env, fs, path::PathBuf};
+
+use crate::template::{
+    aoc_cli::{get_input_path, get_puzzle_path, SubmissionOutcome},
+    client::{AocClient, ClientError},
+    PuzzleId,
+};
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+pub struct HttpClient {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl HttpClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').into(),
+            session: session.trim().into(),
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    /// Configure the client from `AOC_BASE_URL` and the session cookie.
+    pub fn from_env() -> Result<Self, ClientError> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let session = read_session().ok_or(ClientError::MissingSession)?;
+        Ok(Self::new(&base_url, &session))
+    }
+
+    fn day_url(&self, puzzle: PuzzleId) -> String {
+        format!(
+            "{}/{}/day/{}",
+            self.base_url,
+            puzzle.year,
+            puzzle.day.into_inner()
+        )
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    fn get(&self, url: &str) -> Result<String, ClientError> {
+        self.agent
+            .get(url)
+            .set("Cookie", &self.cookie())
+            .call()
+            .map_err(|e| ClientError::Http(e.to_string()))?
+            .into_string()
+            .map_err(ClientError::IO)
+    }
+
+    /// Fetch the puzzle description as markdown and store it in the puzzle file.
+    fn fetch_puzzle(&self, puzzle: PuzzleId) -> Result<String, ClientError> {
+        let html = self.get(&self.day_url(puzzle))?;
+        let markdown = html_to_markdown(&html);
+        fs::write(get_puzzle_path(puzzle), &markdown)?;
+        Ok(markdown)
+    }
+}
+
+impl AocClient for HttpClient {
+    fn download(&self, puzzle: PuzzleId) -> Result<(), ClientError> {
+        let input_path = get_input_path(puzzle);
+        let puzzle_path = get_puzzle_path(puzzle);
+
+        let input = self.get(&format!("{}/input", self.day_url(puzzle)))?;
+        fs::write(&input_path, input)?;
+        self.fetch_puzzle(puzzle)?;
+
+        println!("---");
+        println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+        println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+        Ok(())
+    }
+
+    fn read(&self, puzzle: PuzzleId) -> Result<(), ClientError> {
+        println!("{}", self.fetch_puzzle(puzzle)?);
+        Ok(())
+    }
+
+    fn submit(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmissionOutcome, ClientError> {
+        let html = self
+            .agent
+            .post(&format!("{}/answer", self.day_url(puzzle)))
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|e| ClientError::Http(e.to_string()))?
+            .into_string()?;
+
+        let response = html_to_markdown(&html);
+        println!("{response}");
+        Ok(SubmissionOutcome::from_response(&response))
+    }
+}
+
+/// Read the session cookie from `AOC_SESSION`, the file at `AOC_SESSION_FILE`,
+/// or the default locations used by aoc-cli.
+fn read_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Some(session);
+    }
+
+    let mut paths: Vec<PathBuf> = vec![];
+
+    if let Ok(path) = env::var("AOC_SESSION_FILE") {
+        paths.push(path.into());
+    }
+
+    if let Ok(home) = env::var("HOME").or_else(|_| env::var("USERPROFILE")) {
+        paths.push(PathBuf::from(&home).join(".adventofcode.session"));
+        paths.push(PathBuf::from(&home).join(".config/adventofcode.session"));
+    }
+
+    paths
+        .iter()
+        .find_map(|path| fs::read_to_string(path).ok())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Convert the `<article>` elements of a page to markdown, similar to the puzzle files written by aoc-cli.
+/// Code blocks become fenced blocks, emphasis becomes `*text*`.
+pub fn html_to_markdown(html: &str) -> String {
+    let articles: Vec<&str> = html
+        .split("<article")
+        .skip(1)
+        .filter_map(|s| s.split("</article>").next())
+        .filter_map(|s| s.split_once('>').map(|(_, body)| body))
+        .collect();
+
+    // NOTE: some responses are not wrapped in an article, convert the whole document then.
+    let sources = if articles.is_empty() {
+        vec![html]
+    } else {
+        articles
+    };
+
+    let mut out = String::new();
+    for source in sources {
+        convert_tags(source, &mut out);
+    }
+
+    let mut markdown = out
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    while markdown.contains("\n\n\n") {
+        markdown = markdown.replace("\n\n\n", "\n\n");
+    }
+
+    markdown.trim().to_string()
+}
+
+fn convert_tags(html: &str, out: &mut String) {
+    let mut in_pre = false;
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        out.push_str(&decode_entities(&rest[..start]));
+
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+
+        let is_closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+
+        let replacement = match (name.as_str(), is_closing) {
+            ("h2", false) => "\n\n## ",
+            ("h2" | "p" | "ul", true) => "\n\n",
+            ("p" | "ul", false) => "\n\n",
+            ("li", false) => "\n* ",
+            ("pre", false) => {
+                in_pre = true;
+                "\n\n```\n"
+            }
+            ("pre", true) => {
+                in_pre = false;
+                "```\n\n"
+            }
+            ("code", _) if !in_pre => "`",
+            ("em", _) if !in_pre => "*",
+            ("br", _) => "\n",
+            _ => "",
+        };
+
+        out.push_str(replacement);
+    }
+
+    out.push_str(&decode_entities(rest));
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{html_to_markdown, HttpClient};
+    use crate::{
+        day,
+        template::{aoc_cli::SubmissionOutcome, client::AocClient, PuzzleId, Year},
+    };
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    /// Serve a single request with `body` and return the raw request that was received.
+    fn mock_server(body: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut request_body = vec![0; content_length];
+            reader.read_exact(&mut request_body).unwrap();
+            request.push_str(&String::from_utf8(request_body).unwrap());
+
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+
+            request
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, handle) = mock_server(
+            "<html><main><article><p>That's not the right answer; your answer is too high. \
+            Please wait one minute before trying again.</p></article></main></html>",
+        );
+
+        let client = HttpClient::new(&base_url, "abc\n");
+        let puzzle = PuzzleId::new(Year::new(2016).unwrap(), day!(1));
+        let outcome = client.submit(puzzle, 2, "42").unwrap();
+        assert!(matches!(outcome, SubmissionOutcome::TooHigh(Some(_))));
+
+        let request = handle.join().unwrap();
+        assert!(request.starts_with("POST /2016/day/1/answer HTTP/1.1"));
+        assert!(request.contains("Cookie: session=abc\r\n"));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn converts_puzzles_to_markdown() {
+        let html = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2>
+<p>For example:</p>
+<pre><code>3   4
+<em>4</em>   3
+</code></pre>
+<p>In total, the result is <code><em>11</em></code> &amp; more.</p>
+<ul><li>one</li><li>two</li></ul>
+</article>
+<p>Answer: <input type="text" name="answer"/></p>
+</main></body></html>"#;
+
+        assert_eq!(
+            html_to_markdown(html),
+            [
+                "## --- Day 1: Historian Hysteria ---",
+                "",
+                "For example:",
+                "",
+                "```",
+                "3   4",
+                "4   3",
+                "```",
+                "",
+                "In total, the result is `*11*` & more.",
+                "",
+                "* one",
+                "* two",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,188 @@
+/// Describes where and when a day was benched, so that timings of different machines and commits can be told apart.
+use std::{
+    collections::HashMap,
+    env, fs,
+    process::Command,
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+/// Where and when a day was benched.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BenchMetadata {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Short hash of the commit, with a `-dirty` suffix if tracked files had changes.
+    pub commit: Option<String>,
+    /// Version of the compiler that built the solutions, e.g. `rustc 1.83.0 (90b35a623 2024-11-26)`.
+    pub rustc: Option<String>,
+    /// Host name and CPU model, e.g. `builder (AMD Ryzen 7 5800X, 16 threads)`.
+    pub host: Option<String>,
+}
+
+impl BenchMetadata {
+    /// Describe the current machine and working tree. Details that can not be determined are left out.
+    pub fn collect() -> Self {
+        BenchMetadata {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            commit: commit(),
+            rustc: option_env!("AOC_RUSTC_VERSION").map(str::to_string),
+            host: host(),
+        }
+    }
+
+    /// Day the timings were taken in UTC, e.g. `2024-12-22`.
+    pub fn date(&self) -> String {
+        // civil date of a day since the epoch, see http://howardhinnant.github.io/date_algorithms.html
+        let days = self.timestamp / 86_400 + 719_468;
+        let era = days / 146_097;
+        let day_of_era = days - era * 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+        format!("{year:04}-{month:02}-{day:02}")
+    }
+}
+
+/// Run a command and return its trimmed output, if it succeeded.
+fn output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn commit() -> Option<String> {
+    let hash = output("git", &["rev-parse", "--short", "HEAD"])?;
+    let is_dirty = output("git", &["status", "--porcelain", "--untracked-files=no"])
+        .is_some_and(|status| !status.is_empty());
+
+    Some(if is_dirty {
+        format!("{hash}-dirty")
+    } else {
+        hash
+    })
+}
+
+fn host() -> Option<String> {
+    let name = fs::read_to_string("/proc/sys/kernel/hostname")
+        .ok()
+        .map(|name| name.trim().to_string())
+        .or_else(|| env::var("HOSTNAME").ok())
+        .or_else(|| env::var("COMPUTERNAME").ok())
+        .or_else(|| output("hostname", &[]))
+        .filter(|name| !name.is_empty());
+
+    let threads = match thread::available_parallelism().map_or(1, usize::from) {
+        1 => "1 thread".to_string(),
+        n => format!("{n} threads"),
+    };
+    let cpu = cpu_model().map(|model| format!("{model}, {threads}"));
+
+    match (name, cpu) {
+        (Some(name), Some(cpu)) => Some(format!("{name} ({cpu})")),
+        (name, cpu) => name.or(cpu),
+    }
+}
+
+fn cpu_model() -> Option<String> {
+    fs::read_to_string("/proc/cpuinfo")
+        .ok()
+        .and_then(|cpuinfo| {
+            cpuinfo
+                .lines()
+                .find(|line| line.starts_with("model name"))
+                .and_then(|line| line.split_once(':'))
+                .map(|(_, model)| model.trim().to_string())
+        })
+        .or_else(|| output("sysctl", &["-n", "machdep.cpu.brand_string"]))
+        .filter(|model| !model.is_empty())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&BenchMetadata> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &BenchMetadata) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        for (key, field) in [
+            ("commit", &value.commit),
+            ("rustc", &value.rustc),
+            ("host", &value.host),
+        ] {
+            map.insert(
+                key.into(),
+                field.clone().map_or(JsonValue::Null, JsonValue::String),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchMetadata {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected metadata to be a JSON object.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected metadata.timestamp to be a number.")? as u64;
+
+        let string = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(JsonValue::String(s)) => Ok(Some(s.clone())),
+            Some(_) => Err(format!("Expected metadata.{key} to be null or string.")),
+        };
+
+        Ok(BenchMetadata {
+            timestamp,
+            commit: string("commit")?,
+            rustc: string("rustc")?,
+            host: string("host")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::BenchMetadata;
+
+    #[test]
+    fn formats_dates() {
+        let date = |timestamp| {
+            BenchMetadata {
+                timestamp,
+                ..Default::default()
+            }
+            .date()
+        };
+
+        assert_eq!(date(0), "1970-01-01");
+        assert_eq!(date(1_733_029_200), "2024-12-01");
+        assert_eq!(date(1_709_164_800), "2024-02-29");
+        assert_eq!(date(1_735_689_599), "2024-12-31");
+    }
+}
@@ -0,0 +1,120 @@
+/// Backends that talk to advent of code: the "aoc-cli" command-line or the built-in HTTP client.
+/// The backend is selected via the `AOC_CLIENT` environment variable (`aoc-cli` or `native`).
+/// If the variable is not set, aoc-cli is used when installed and the HTTP client otherwise.
+use std::{env, fmt::Display, io};
+
+use crate::template::{
+    aoc_cli::{self, AocCommandError, SubmissionOutcome},
+    aoc_http::HttpClient,
+    PuzzleId,
+};
+
+pub trait AocClient {
+    /// Download the input and the puzzle description to `data/`.
+    fn download(&self, puzzle: PuzzleId) -> Result<(), ClientError>;
+
+    /// Print the puzzle description.
+    fn read(&self, puzzle: PuzzleId) -> Result<(), ClientError>;
+
+    /// Submit an answer and return the response of advent of code.
+    fn submit(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmissionOutcome, ClientError>;
+}
+
+#[derive(Debug)]
+pub enum ClientError {
+    AocCli(AocCommandError),
+    UnknownBackend(String),
+    MissingSession,
+    Http(String),
+    IO(io::Error),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::AocCli(AocCommandError::CommandNotFound) => write!(
+                f,
+                "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+            ),
+            ClientError::AocCli(e) => write!(f, "failed to call aoc-cli: {e}"),
+            ClientError::UnknownBackend(x) => write!(
+                f,
+                "unknown client `{x}` in AOC_CLIENT, expecting `aoc-cli` or `native`."
+            ),
+            ClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set AOC_SESSION, point AOC_SESSION_FILE to a file, \
+                or create \"~/.adventofcode.session\"."
+            ),
+            ClientError::Http(e) => write!(f, "request to advent of code failed: {e}"),
+            ClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<AocCommandError> for ClientError {
+    fn from(e: AocCommandError) -> Self {
+        ClientError::AocCli(e)
+    }
+}
+
+impl From<io::Error> for ClientError {
+    fn from(e: io::Error) -> Self {
+        ClientError::IO(e)
+    }
+}
+
+/// Select the backend as configured by the environment.
+pub fn from_env() -> Result<Box<dyn AocClient>, ClientError> {
+    match env::var("AOC_CLIENT").ok().as_deref() {
+        Some("aoc-cli") => {
+            aoc_cli::check()?;
+            Ok(Box::new(AocCli))
+        }
+        Some("native") => Ok(Box::new(HttpClient::from_env()?)),
+        Some(x) => Err(ClientError::UnknownBackend(x.into())),
+        None => {
+            if aoc_cli::check().is_ok() {
+                Ok(Box::new(AocCli))
+            } else {
+                match HttpClient::from_env() {
+                    Ok(client) => Ok(Box::new(client)),
+                    // NOTE: neither backend is usable, point to the default one.
+                    Err(ClientError::MissingSession) => {
+                        Err(AocCommandError::CommandNotFound.into())
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+        }
+    }
+}
+
+/// Backend that calls the "aoc-cli" command-line.
+pub struct AocCli;
+
+impl AocClient for AocCli {
+    fn download(&self, puzzle: PuzzleId) -> Result<(), ClientError> {
+        aoc_cli::download(puzzle)?;
+        Ok(())
+    }
+
+    fn read(&self, puzzle: PuzzleId) -> Result<(), ClientError> {
+        aoc_cli::read(puzzle)?;
+        Ok(())
+    }
+
+    fn submit(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmissionOutcome, ClientError> {
+        Ok(aoc_cli::submit(puzzle, part, answer)?)
+    }
+}
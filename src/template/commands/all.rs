@@ -1,5 +1,5 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::{all_days, run_multi::run_multi, InputSource, RunMode, Year};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(year: Year, mode: RunMode, input: InputSource) {
+    run_multi(year, &all_days().collect(), mode, false, input);
 }
@@ -1,14 +1,28 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use std::{fs, process};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+use crate::template::{
+    client,
+    progress::{self, Progress},
+    PuzzleId,
+};
+
+pub fn handle(puzzle: PuzzleId) {
+    // NOTE: years other than the default one may not have their data directories yet.
+    for path in [puzzle.data_path("inputs", None), puzzle.puzzle_path()] {
+        if let Some(dir) = path.parent() {
+            if let Err(e) = fs::create_dir_all(dir) {
+                eprintln!("Failed to create directory \"{}\": {e}", dir.display());
+                process::exit(1);
+            }
+        }
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    let result = client::from_env().and_then(|client| client.download(puzzle));
+
+    if let Err(e) = result {
+        eprintln!("{e}");
         process::exit(1);
     };
+
+    Progress::record(puzzle, |day| day.downloaded = Some(progress::now()));
 }
@@ -1,79 +1,299 @@
-use std::{
-    fs::{File, OpenOptions},
-    io::Write,
-    process,
-};
+use std::{fs, io::ErrorKind, path::Path, process};
 
-use crate::template::Day;
+use crate::template::{
+    examples::{Example, Examples},
+    module_templates,
+    progress::{self, Progress},
+    puzzle_examples::{self, PuzzleExample},
+    PuzzleId,
+};
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+/// Which existing files `scaffold` may replace. Files that do not exist or are empty are always written.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Overwrite {
+    pub module: bool,
+    pub input: bool,
+    pub examples: bool,
+}
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
-    let mut file = OpenOptions::new();
-    if overwrite {
-        file.create(true);
-    } else {
-        file.create_new(true);
-    }
-    file.truncate(true).write(true).open(path)
+/// What happened to a single file.
+enum Outcome {
+    Created,
+    Kept,
+    Overwritten,
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// Write a file unless it already has content, which is only replaced if `overwrite` is set.
+fn write_file(path: &str, contents: &str, overwrite: bool) -> Result<Outcome, std::io::Error> {
+    let outcome = match fs::metadata(path) {
+        Ok(metadata) if metadata.len() > 0 && !overwrite => return Ok(Outcome::Kept),
+        Ok(metadata) if metadata.len() > 0 => Outcome::Overwritten,
+        Ok(_) => Outcome::Created,
+        Err(e) if e.kind() == ErrorKind::NotFound => Outcome::Created,
+        Err(e) => return Err(e),
+    };
+
+    create_parent_dir(path)?;
+    fs::write(path, contents)?;
+    Ok(outcome)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Write a file and print what happened to it. Exits if the file can not be written.
+fn scaffold_file(kind: &str, path: &str, contents: &str, overwrite: bool, flag: &str) -> bool {
+    let empty = if contents.is_empty() { "empty " } else { "" };
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
+    match write_file(path, contents, overwrite) {
+        Ok(Outcome::Created) => {
+            println!("Created {empty}{kind} file \"{path}\"");
+            true
         }
-    };
-
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+        Ok(Outcome::Overwritten) => {
+            println!("Overwrote {kind} file \"{path}\"");
+            true
+        }
+        Ok(Outcome::Kept) => {
+            println!("Kept existing {kind} file \"{path}\" (pass `{flag}` to replace it)");
+            false
         }
         Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
+            eprintln!("Failed to write {kind} file \"{path}\": {e}");
             process::exit(1);
         }
     }
+}
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
-        }
+/// Years other than the default one keep their data in their own directory, which may not exist yet.
+fn create_parent_dir(path: &str) -> Result<(), std::io::Error> {
+    match Path::new(path).parent() {
+        Some(dir) => fs::create_dir_all(dir),
+        None => Ok(()),
+    }
+}
+
+/// Title of a puzzle description, e.g. `Day 1: Historian Hysteria`.
+fn puzzle_title(markdown: &str) -> Option<String> {
+    markdown
+        .lines()
+        .find_map(|line| line.strip_prefix("## --- "))
+        .map(|title| title.trim_end_matches(" ---").trim().to_string())
+}
+
+/// Suffix of the example file at `index`, e.g. `01.txt` for the first example and `01-2.txt` for the second.
+fn example_suffix(index: usize) -> Option<u8> {
+    (index > 0).then(|| u8::try_from(index + 1).unwrap_or(u8::MAX))
+}
+
+/// Example file and expected answer of a part's test, taken from the first example that has an answer for it.
+fn test_placeholders(examples: &[PuzzleExample], part: u8) -> (String, String) {
+    let found = examples.iter().enumerate().find_map(|(i, example)| {
+        let answer = match part {
+            1 => example.part_1.as_ref(),
+            _ => example.part_2.as_ref(),
+        };
+        answer.map(|answer| (example_suffix(i), answer))
+    });
+
+    match found {
+        Some((Some(suffix), answer)) => (
+            format!("read_file_part(\"examples\", PUZZLE, {suffix})"),
+            answer_literal(answer),
+        ),
+        Some((None, answer)) => (
+            "read_file(\"examples\", PUZZLE)".to_string(),
+            answer_literal(answer),
+        ),
+        None => (
+            "read_file(\"examples\", PUZZLE)".to_string(),
+            "None".to_string(),
+        ),
+    }
+}
+
+fn answer_literal(answer: &str) -> String {
+    if answer.parse::<u64>().is_ok() {
+        format!("Some({answer})")
+    } else {
+        format!("Some({answer:?}.to_string())")
+    }
+}
+
+/// Record the expected answers of the written examples in `data/examples.json`, replacing earlier entries.
+fn store_expected_answers(
+    puzzle: PuzzleId,
+    written: &[(Option<u8>, &PuzzleExample)],
+) -> Result<usize, String> {
+    let entries: Vec<Example> = written
+        .iter()
+        .filter(|(_, example)| example.part_1.is_some() || example.part_2.is_some())
+        .map(|(suffix, example)| Example {
+            file: file_name(&puzzle.data_path("examples", *suffix)),
+            part_1: example.part_1.clone(),
+            part_2: example.part_2.clone(),
+            params: vec![],
+        })
+        .collect();
+
+    if entries.is_empty() {
+        return Ok(0);
+    }
+
+    let mut manifest = Examples::read_from_file(puzzle.year)?;
+    manifest
+        .data
+        .retain(|example| entries.iter().all(|entry| entry.file != example.file));
+    manifest.data.extend(entries.iter().cloned());
+    manifest
+        .store_file(puzzle.year)
+        .map_err(|e| e.to_string())?;
+
+    Ok(entries.len())
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Scaffold a solution from the given template, or the default one.
+/// Existing files with content are kept, unless `overwrite` allows replacing them.
+pub fn handle(puzzle: PuzzleId, overwrite: Overwrite, template: Option<&str>) {
+    let template =
+        match module_templates::load(template.unwrap_or(module_templates::DEFAULT_TEMPLATE)) {
+            Ok(template) => template,
+            Err(e) => {
+                eprintln!("Failed to load template: {e}");
+                process::exit(1);
+            }
+        };
+
+    let markdown = fs::read_to_string(puzzle.puzzle_path()).ok();
+    let examples = markdown
+        .as_deref()
+        .map(puzzle_examples::extract)
+        .unwrap_or_default();
+
+    let puzzle_title = markdown.as_deref().and_then(puzzle_title);
+    let title = puzzle_title
+        .clone()
+        .unwrap_or_else(|| format!("Day {}", puzzle.day.into_inner()));
+    let (part_one_example, part_one_answer) = test_placeholders(&examples, 1);
+    let (part_two_example, part_two_answer) = test_placeholders(&examples, 2);
+
+    let module = module_templates::render(
+        &template,
+        &[
+            ("DAY_NUMBER", puzzle.day.into_inner().to_string()),
+            ("DAY", puzzle.day.to_string()),
+            ("YEAR", puzzle.year.to_string()),
+            ("TITLE", title),
+            ("PART_ONE_EXAMPLE", part_one_example),
+            ("PART_ONE_ANSWER", part_one_answer),
+            ("PART_TWO_EXAMPLE", part_two_example),
+            ("PART_TWO_ANSWER", part_two_answer),
+        ],
+    );
+
+    scaffold_file(
+        "module",
+        &puzzle.bin_path(),
+        &module,
+        overwrite.module,
+        "--overwrite-module",
+    );
+
+    // NOTE: the input may already have been downloaded, it is never replaced by an empty file unless asked to.
+    scaffold_file(
+        "input",
+        &puzzle.data_path("inputs", None).display().to_string(),
+        "",
+        overwrite.input,
+        "--overwrite-input",
+    );
+
+    let mut written = vec![];
+
+    if examples.is_empty() {
+        scaffold_file(
+            "example",
+            &puzzle.data_path("examples", None).display().to_string(),
+            "",
+            overwrite.examples,
+            "--overwrite-examples",
+        );
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    for (i, example) in examples.iter().enumerate() {
+        let suffix = example_suffix(i);
+        let path = puzzle.data_path("examples", suffix).display().to_string();
+
+        if scaffold_file(
+            "example",
+            &path,
+            &example.input,
+            overwrite.examples,
+            "--overwrite-examples",
+        ) {
+            written.push((suffix, example));
         }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+    }
+
+    // NOTE: answers of kept examples are kept as well, as they may not match the extracted ones.
+    match store_expected_answers(puzzle, &written) {
+        Ok(0) => {}
+        Ok(count) => {
+            println!(
+                "Added {count} example(s) with expected answers to \"{}\"",
+                Examples::file_path(puzzle.year).display()
+            );
         }
+        Err(e) => eprintln!("Failed to store expected answers: {e}"),
     }
 
+    Progress::record(puzzle, |day| {
+        day.scaffolded.get_or_insert_with(progress::now);
+        if puzzle_title.is_some() {
+            day.title = puzzle_title;
+        }
+    });
+
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    if puzzle.year.is_default() {
+        println!("🎄 Type `cargo solve {}` to run your solution.", puzzle.day);
+    } else {
+        println!(
+            "🎄 Type `cargo solve {} --year {}` to run your solution.",
+            puzzle.day, puzzle.year
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{write_file, Outcome};
+    use std::{env, fs};
+
+    #[test]
+    fn keeps_files_with_content() {
+        let dir = env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let path = dir.join("inputs").join("01.txt").display().to_string();
+
+        assert!(matches!(write_file(&path, "", false), Ok(Outcome::Created)));
+        assert!(matches!(
+            write_file(&path, "1 2\n", false),
+            Ok(Outcome::Created)
+        ));
+        assert!(matches!(write_file(&path, "", false), Ok(Outcome::Kept)));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1 2\n");
+
+        assert!(matches!(
+            write_file(&path, "", true),
+            Ok(Outcome::Overwritten)
+        ));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
 }
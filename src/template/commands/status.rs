@@ -0,0 +1,158 @@
+use std::{fs, path::Path, time::SystemTime};
+
+use crate::template::{
+    all_days,
+    progress::{self, DayProgress, Progress},
+    readme_stars,
+    submissions::Submissions,
+    timings::Timings,
+    PuzzleId, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+
+/// Whether the stored benchmark of a day reflects its current solution.
+#[derive(Debug, PartialEq, Eq)]
+enum Benchmark {
+    Current,
+    /// The solution changed after the timings were stored.
+    Outdated,
+    Missing,
+}
+
+impl Benchmark {
+    fn label(&self) -> &'static str {
+        match self {
+            Benchmark::Current => "current",
+            Benchmark::Outdated => "outdated",
+            Benchmark::Missing => "missing",
+        }
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn benchmark(
+    puzzle: PuzzleId,
+    timings: &Timings,
+    timings_modified: Option<SystemTime>,
+) -> Benchmark {
+    if !timings.is_day_complete(puzzle.day) {
+        return Benchmark::Missing;
+    }
+
+    match (modified(Path::new(&puzzle.bin_path())), timings_modified) {
+        (Some(solution), Some(timings)) if solution > timings => Benchmark::Outdated,
+        _ => Benchmark::Current,
+    }
+}
+
+/// Format a duration in seconds with its two most significant units, e.g. `2d 03h` or `14m 09s`.
+fn format_elapsed(seconds: u64) -> String {
+    let (days, hours, minutes) = (seconds / 86_400, seconds / 3_600 % 24, seconds / 60 % 60);
+
+    match (days, hours, minutes) {
+        (0, 0, m) => format!("{m}m {:02}s", seconds % 60),
+        (0, h, m) => format!("{h}h {m:02}m"),
+        (d, h, _) => format!("{d}d {h:02}h"),
+    }
+}
+
+/// Time from the unlock of a puzzle to the acceptance of a part, `-` if it was never accepted.
+/// Parts submitted before progress was tracked have a star, but no time.
+fn solve_time(puzzle: PuzzleId, progress: Option<&DayProgress>, part: u8, star: bool) -> String {
+    match progress.and_then(|p| p.accepted(part)) {
+        Some(accepted) => format_elapsed(accepted.saturating_sub(progress::unlock_time(puzzle))),
+        None if star => "?".into(),
+        None => "-".into(),
+    }
+}
+
+/// Print the progress of a year. If `store` is set, the stars table of the readme is updated as well.
+pub fn handle(year: Year, store: bool) {
+    let progress = Progress::read_from_file(year).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        Progress::default()
+    });
+    let submissions = Submissions::read_from_file(year).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        eprintln!("Stars of parts accepted before progress was tracked are not counted.");
+        Submissions::default()
+    });
+    let timings_modified = modified(&Timings::file_path(year));
+    let timings = if timings_modified.is_some() {
+        Timings::read_from_file(year).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            Timings::default()
+        })
+    } else {
+        Timings::default()
+    };
+    let now = progress::now();
+
+    let mut rows = vec![];
+    let mut stars = 0;
+
+    for day in all_days() {
+        let puzzle = PuzzleId::new(year, day);
+
+        if now < progress::unlock_time(puzzle) {
+            rows.push(format!("{ANSI_ITALIC}{day}   locked{ANSI_RESET}"));
+            continue;
+        }
+
+        let entry = progress.get(day);
+        let star_1 = progress.has_star(&submissions, day, 1);
+        let star_2 = progress.has_star(&submissions, day, 2);
+        stars += u8::from(star_1) + u8::from(star_2);
+
+        let scaffolded =
+            entry.is_some_and(|p| p.scaffolded.is_some()) || Path::new(&puzzle.bin_path()).exists();
+        let benchmark = if scaffolded {
+            benchmark(puzzle, &timings, timings_modified).label()
+        } else {
+            "-"
+        };
+
+        let star = |s: bool| if s { '★' } else { '☆' };
+        rows.push(format!(
+            "{day}   {}{}    {:>7}   {:>7}   {benchmark:<9}  {}",
+            star(star_1),
+            star(star_2),
+            solve_time(puzzle, entry, 1, star_1),
+            solve_time(puzzle, entry, 2, star_2),
+            entry.and_then(DayProgress::short_title).unwrap_or_default(),
+        ));
+    }
+
+    println!("{ANSI_BOLD}Advent of Code {year}{ANSI_RESET}: {stars}/50 ★");
+    println!();
+    println!("Day  Stars  Part 1    Part 2   Benchmark  Title");
+    for row in rows {
+        println!("{row}");
+    }
+
+    if store {
+        println!();
+        match readme_stars::update(year) {
+            Ok(true) => println!("Stored updated stars."),
+            Ok(false) => println!("No stars recorded yet, kept the stars table."),
+            Err(_) => eprintln!("Failed to store updated stars."),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::format_elapsed;
+
+    #[test]
+    fn formats_elapsed_times() {
+        assert_eq!(format_elapsed(9), "0m 09s");
+        assert_eq!(format_elapsed(14 * 60 + 9), "14m 09s");
+        assert_eq!(format_elapsed(3 * 3_600 + 5 * 60), "3h 05m");
+        assert_eq!(format_elapsed(2 * 86_400 + 3 * 3_600 + 59), "2d 03h");
+    }
+}
@@ -1,16 +1,46 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, process};
 
+use crate::template::bench_metadata::BenchMetadata;
+use crate::template::compare::{self, Threshold};
 use crate::template::run_multi::run_multi;
+use crate::template::timing_history::TimingHistory;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::worktree::Worktree;
+use crate::template::{
+    all_days, readme_benchmarks, readme_stars, Day, InputSource, Limits, PuzzleId, RunMode, Year,
+    ANSI_BOLD, ANSI_RESET,
+};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Bench solutions. If a threshold to `compare` with is given, the run is compared with the stored timings
+/// and the process exits with a non-zero status code if any part regressed.
+pub fn handle(
+    year: Year,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    compare: Option<Threshold>,
+    mode: RunMode,
+) {
+    // NOTE: unreadable timings are not replaced by the timings of this run. Without `--store`, they are ignored.
+    let stored_timings = match Timings::read_from_file(year) {
+        Ok(timings) => timings,
+        Err(e) if store => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            Timings::default()
+        }
+    };
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
                 all_days().collect()
+            } else if compare.is_some() {
+                // comparisons are only possible for days that have been benched before.
+                stored_timings.data.iter().map(|t| t.day).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
                 all_days()
@@ -21,14 +51,48 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let mut timings = run_multi(year, &days_to_run, mode, true, InputSource::Puzzle).unwrap();
+
+    let deltas = compare::compare(&stored_timings, &timings);
+    let regressions = compare.map_or(0, |threshold| {
+        let missing = compare::missing(&stored_timings, &timings, &days_to_run);
+        compare::print_report(&deltas, &missing, threshold)
+    });
 
     if store {
+        let metadata = BenchMetadata::collect();
+        for timing in &mut timings.data {
+            timing.metadata = Some(metadata.clone());
+        }
+
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(year).unwrap();
+
+        // NOTE: an unreadable history is left alone instead of being replaced by this run.
+        match TimingHistory::read_from_file(year) {
+            Ok(mut history) => {
+                history.append(&timings);
+                if history.store_file(year).is_err() {
+                    eprintln!("Failed to store benchmark history.");
+                }
+            }
+            Err(e) => {
+                eprintln!(
+                    "Failed to read \"{}\", the run was not added to it: {e}",
+                    TimingHistory::file_path(year).display()
+                );
+            }
+        }
+
+        // NOTE: the readme marks regressions against the default threshold unless another one was given.
+        let threshold = compare.unwrap_or_default();
+        let regressed: Vec<_> = deltas
+            .into_iter()
+            .filter(|delta| delta.is_regression(threshold))
+            .collect();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(year, merged_timings, &regressed) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -36,5 +100,98 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
                 eprintln!("Failed to store updated benchmarks.");
             }
         }
+
+        if readme_stars::update(year).is_err() {
+            eprintln!("Failed to store updated stars.");
+        }
+    }
+
+    if regressions > 0 {
+        process::exit(1);
     }
 }
+
+/// Print how the stored timings of a day, or of all days, developed over time.
+pub fn history(year: Year, day: Option<Day>, csv: bool) {
+    let history = match TimingHistory::read_from_file(year) {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!(
+                "Failed to read \"{}\": {e}",
+                TimingHistory::file_path(year).display()
+            );
+            process::exit(1);
+        }
+    };
+
+    if csv {
+        println!("{}", history.to_csv(day));
+    } else {
+        history.print_report(day);
+    }
+}
+
+/// Bench the solutions of `rev` and of the working tree and print their durations side by side.
+/// Both trees are benched in isolated release builds, `rev` is checked out into a temporary worktree.
+pub fn against(year: Year, day: Option<Day>, rev: &str, limits: Limits) {
+    let days_to_run = day.map_or_else(|| all_days().collect(), |day| HashSet::from([day]));
+
+    let worktree = match Worktree::checkout(rev) {
+        Ok(worktree) => worktree,
+        Err(e) => {
+            eprintln!("Failed to check out {rev}: {e}");
+            process::exit(1);
+        }
+    };
+
+    // NOTE: puzzle inputs are usually not committed, both trees are benched with the inputs of the working tree.
+    for day in &days_to_run {
+        let input = PuzzleId::new(year, *day).data_path("inputs", None);
+        if input.exists() {
+            if let Err(e) = worktree.copy_file(&input) {
+                eprintln!(
+                    "Failed to copy \"{}\" to the worktree: {e}",
+                    input.display()
+                );
+            }
+        }
+    }
+
+    let mode = |root| RunMode::Isolated {
+        is_release: true,
+        limits,
+        jobs: 1,
+        root,
+    };
+
+    println!(
+        "{ANSI_BOLD}Benching {rev} ({}){ANSI_RESET}\n",
+        worktree.commit
+    );
+    let reference = run_multi(
+        year,
+        &days_to_run,
+        mode(Some(worktree.path())),
+        true,
+        InputSource::Puzzle,
+    )
+    .unwrap();
+
+    // NOTE: solutions of a commit before results were reported through `AOC_RESULTS_FILE` run, but report nothing.
+    if reference.data.is_empty() && !days_to_run.is_empty() {
+        eprintln!(
+            "\n{rev} reported no results. Its solutions either do not build or predate results being reported through `AOC_RESULTS_FILE`, which `--against` needs."
+        );
+        drop(worktree);
+        process::exit(1);
+    }
+
+    println!("\n{ANSI_BOLD}Benching the working tree{ANSI_RESET}\n");
+    let current = run_multi(year, &days_to_run, mode(None), true, InputSource::Puzzle).unwrap();
+
+    compare::print_speedups(
+        &compare::compare(&reference, &current),
+        &compare::missing(&reference, &current, &days_to_run),
+        rev,
+    );
+}
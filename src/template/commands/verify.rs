@@ -0,0 +1,131 @@
+use std::{collections::HashSet, path::Path, process};
+
+use crate::template::{
+    all_days, answers::Answers, results::PartResult, run_multi::run_days, Day, InputSource,
+    PuzzleId, RunMode, Year, ANSI_BOLD, ANSI_RESET,
+};
+
+/// Outcome of comparing a part's answer with the recorded one.
+#[derive(Debug, PartialEq, Eq)]
+enum Verdict {
+    Pass,
+    Fail,
+    Missing,
+}
+
+fn verdict(expected: Option<&str>, actual: Option<&str>) -> Verdict {
+    match (expected, actual) {
+        (None, _) => Verdict::Missing,
+        (Some(expected), Some(actual)) if expected == actual => Verdict::Pass,
+        _ => Verdict::Fail,
+    }
+}
+
+pub fn handle(year: Year, mode: RunMode, store: bool) {
+    // NOTE: an unreadable answers file would be replaced by the answers of this run, so nothing is verified.
+    let mut answers = Answers::read_from_file(year).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
+
+    // NOTE: days that are neither scaffolded nor have recorded answers are skipped.
+    let days_to_run: HashSet<Day> = all_days()
+        .filter(|day| {
+            Path::new(&PuzzleId::new(year, *day).bin_path()).exists()
+                || answers.data.iter().any(|a| a.day == *day)
+        })
+        .collect();
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    let mut recorded = false;
+
+    run_days(
+        year,
+        &days_to_run,
+        mode,
+        false,
+        InputSource::Puzzle,
+        |day, results| {
+            let mut lines = vec![];
+
+            for part in 1..=2 {
+                let actual = find_answer(results, part);
+                let expected = answers.get(day, part).map(str::to_string);
+
+                match verdict(expected.as_deref(), actual) {
+                    Verdict::Pass => {
+                        passed += 1;
+                        lines.push(format!("Part {part}: ✔ pass"));
+                    }
+                    Verdict::Fail => {
+                        failed += 1;
+                        lines.push(format!(
+                            "Part {part}: ✖ fail (expected {}, got {})",
+                            expected.unwrap_or_default(),
+                            actual.unwrap_or("nothing")
+                        ));
+                    }
+                    Verdict::Missing => match actual {
+                        Some(actual) if store => {
+                            answers.set(day, part, actual);
+                            recorded = true;
+                            lines.push(format!("Part {part}: recorded {actual}"));
+                        }
+                        Some(_) => {
+                            missing += 1;
+                            lines.push(format!("Part {part}: ? no recorded answer"));
+                        }
+                        None => {}
+                    },
+                }
+            }
+
+            if !lines.is_empty() {
+                println!("\n{}", lines.join("\n"));
+            }
+        },
+    );
+
+    if recorded {
+        if let Err(e) = answers.store_file(year) {
+            eprintln!("Failed to store answers: {e}");
+        }
+    }
+
+    println!(
+        "\n{ANSI_BOLD}Verified:{ANSI_RESET} {passed} passed, {failed} failed, {missing} missing."
+    );
+
+    if failed > 0 {
+        process::exit(1);
+    }
+}
+
+fn find_answer(results: &[PartResult], part: u8) -> Option<&str> {
+    results
+        .iter()
+        .find(|r| r.part == part)
+        .and_then(|r| r.answer.as_deref())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{verdict, Verdict};
+
+    #[test]
+    fn passes_matching_answers() {
+        assert_eq!(verdict(Some("42"), Some("42")), Verdict::Pass);
+    }
+
+    #[test]
+    fn fails_mismatching_or_absent_answers() {
+        assert_eq!(verdict(Some("42"), Some("43")), Verdict::Fail);
+        assert_eq!(verdict(Some("42"), None), Verdict::Fail);
+    }
+
+    #[test]
+    fn reports_missing_expectations() {
+        assert_eq!(verdict(None, Some("42")), Verdict::Missing);
+        assert_eq!(verdict(None, None), Verdict::Missing);
+    }
+}
@@ -0,0 +1,411 @@
+/// Compares a benchmark run with stored timings to detect performance regressions.
+use std::{collections::HashSet, fmt::Display, str::FromStr, time::Duration};
+
+use crate::template::{
+    results::Failure,
+    timings::{parse_duration, Timings},
+    Day, ANSI_BOLD, ANSI_RESET,
+};
+
+/// How much slower a part may get before it counts as a regression.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Threshold {
+    /// Relative to the stored duration, in percent.
+    Percent(f64),
+    /// Absolute increase of the duration.
+    Absolute(Duration),
+}
+
+impl Default for Threshold {
+    fn default() -> Self {
+        Threshold::Percent(10.0)
+    }
+}
+
+impl FromStr for Threshold {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(percent) = s.strip_suffix('%') {
+            return percent
+                .parse::<f64>()
+                .ok()
+                .filter(|p| *p >= 0.0)
+                .map(Threshold::Percent)
+                .ok_or(format!("expected a positive percentage, got `{s}`."));
+        }
+
+        parse_duration(s).map(Threshold::Absolute).ok_or(format!(
+            "expected a percentage like `10%` or a duration like `2ms`, got `{s}`."
+        ))
+    }
+}
+
+impl Display for Threshold {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Threshold::Percent(p) => write!(f, "{p}%"),
+            Threshold::Absolute(d) => write!(f, "{d:.1?}"),
+        }
+    }
+}
+
+/// How many times faster `current` is than `stored`, below `1.0` if it is slower.
+#[allow(clippy::cast_precision_loss)]
+fn speedup(stored: Duration, current: Duration) -> f64 {
+    let current = current.as_nanos() as f64;
+    if current == 0.0 {
+        return 1.0;
+    }
+    stored.as_nanos() as f64 / current
+}
+
+/// Change of a part's duration between the stored and the new timings.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartDelta {
+    pub day: Day,
+    pub part: u8,
+    pub stored: Duration,
+    pub current: Duration,
+}
+
+impl PartDelta {
+    /// Relative change in percent, positive if the part got slower.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn percent(&self) -> f64 {
+        let stored = self.stored.as_nanos() as f64;
+        let current = self.current.as_nanos() as f64;
+        if stored == 0.0 {
+            return 0.0;
+        }
+        (current - stored) / stored * 100.0
+    }
+
+    /// How many times faster the part got, below `1.0` if it got slower.
+    pub fn speedup(&self) -> f64 {
+        speedup(self.stored, self.current)
+    }
+
+    pub fn is_regression(&self, threshold: Threshold) -> bool {
+        match threshold {
+            Threshold::Percent(p) => self.percent() > p,
+            Threshold::Absolute(d) => self.current > self.stored + d,
+        }
+    }
+}
+
+/// A part with a stored timing that has no timing in the new run, e.g. because it panicked or timed out.
+/// Always counts as a regression.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MissingPart {
+    pub day: Day,
+    pub part: u8,
+    pub stored: Duration,
+    /// Why the part did not finish, if it was reported.
+    pub failure: Option<Failure>,
+}
+
+impl MissingPart {
+    fn reason(&self) -> String {
+        self.failure
+            .as_ref()
+            .map_or_else(|| "no result".into(), ToString::to_string)
+    }
+}
+
+/// Label of a part in a report, with the part number `0` standing for the parse step.
+fn step(part: u8) -> String {
+    match part {
+        0 => "parse".into(),
+        part => format!("part {part}"),
+    }
+}
+
+/// Pair every part of `current` with its stored timing. Parts without a stored timing are skipped.
+pub fn compare(stored: &Timings, current: &Timings) -> Vec<PartDelta> {
+    let mut deltas = vec![];
+
+    for timing in &current.data {
+        let Some(stored_timing) = stored.data.iter().find(|t| t.day == timing.day) else {
+            continue;
+        };
+
+        for part in 0..=2 {
+            if let (Some(stored), Some(current)) = (
+                stored_timing.part_duration(part),
+                timing.part_duration(part),
+            ) {
+                deltas.push(PartDelta {
+                    day: timing.day,
+                    part,
+                    stored,
+                    current,
+                });
+            }
+        }
+    }
+
+    deltas
+}
+
+/// Parts of the `days` that ran which have a stored timing, but no timing in `current`.
+/// This includes every stored part of a day that did not report any result.
+pub fn missing(stored: &Timings, current: &Timings, days: &HashSet<Day>) -> Vec<MissingPart> {
+    let mut missing = vec![];
+
+    for stored_timing in stored.data.iter().filter(|t| days.contains(&t.day)) {
+        let timing = current.data.iter().find(|t| t.day == stored_timing.day);
+
+        for part in 0..=2 {
+            let Some(stored) = stored_timing.part_duration(part) else {
+                continue;
+            };
+
+            if timing.and_then(|t| t.part_duration(part)).is_some() {
+                continue;
+            }
+
+            let failure = timing
+                .and_then(|t| t.failures.iter().find(|(p, _)| *p == part))
+                .map(|(_, failure)| failure.clone());
+
+            missing.push(MissingPart {
+                day: stored_timing.day,
+                part,
+                stored,
+                failure,
+            });
+        }
+    }
+
+    missing
+}
+
+/// Print the deltas and the missing parts, and return the number of regressions.
+pub fn print_report(deltas: &[PartDelta], missing: &[MissingPart], threshold: Threshold) -> usize {
+    println!("\n{ANSI_BOLD}Comparison with stored timings{ANSI_RESET} (threshold {threshold})");
+
+    if deltas.is_empty() && missing.is_empty() {
+        println!("No stored timings to compare with.");
+        return 0;
+    }
+
+    let mut regressions = 0;
+
+    for delta in deltas {
+        let marker = if delta.is_regression(threshold) {
+            regressions += 1;
+            " ✖ regression"
+        } else {
+            ""
+        };
+
+        println!(
+            "Day {} {}: {:.1?} → {:.1?} ({:+.1}%){marker}",
+            delta.day,
+            step(delta.part),
+            delta.stored,
+            delta.current,
+            delta.percent()
+        );
+    }
+
+    for part in missing {
+        regressions += 1;
+        println!(
+            "Day {} {}: {:.1?} → {} ✖ regression",
+            part.day,
+            step(part.part),
+            part.stored,
+            part.reason()
+        );
+    }
+
+    println!(
+        "{regressions} regression(s) in {} part(s).",
+        deltas.len() + missing.len()
+    );
+    regressions
+}
+
+/// Print the durations of the `reference` tree and the working tree side by side, with the speedup of every part.
+/// The deltas are expected to store the durations of the reference tree. Parts that only finished in the reference
+/// tree are listed with the reason they did not finish in the working tree.
+pub fn print_speedups(deltas: &[PartDelta], missing: &[MissingPart], reference: &str) {
+    println!("\n{ANSI_BOLD}Comparison of {reference} with the working tree{ANSI_RESET}");
+
+    if deltas.is_empty() && missing.is_empty() {
+        println!("No part was benched in both trees.");
+        return;
+    }
+
+    let width = reference.chars().count().max(10);
+    println!(
+        "Day  Part    {reference:>width$}  {:>12}  Speedup",
+        "Working tree"
+    );
+
+    for delta in deltas {
+        println!(
+            "{}   {:<6}  {:>width$}  {:>12}  {:.2}×",
+            delta.day,
+            step(delta.part),
+            format!("{:.1?}", delta.stored),
+            format!("{:.1?}", delta.current),
+            delta.speedup()
+        );
+    }
+
+    for part in missing {
+        println!(
+            "{}   {:<6}  {:>width$}  {:>12}  ✖ {}",
+            part.day,
+            step(part.part),
+            format!("{:.1?}", part.stored),
+            "-",
+            part.reason()
+        );
+    }
+
+    let stored: Duration = deltas.iter().map(|d| d.stored).sum();
+    let current: Duration = deltas.iter().map(|d| d.current).sum();
+    println!(
+        "{ANSI_BOLD}Total{ANSI_RESET}        {:>width$}  {:>12}  {:.2}×",
+        format!("{stored:.1?}"),
+        format!("{current:.1?}"),
+        speedup(stored, current)
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, missing, MissingPart, PartDelta, Threshold};
+    use crate::{
+        day,
+        template::{
+            results::Failure,
+            timings::{Timing, Timings},
+        },
+    };
+    use std::{collections::HashSet, time::Duration};
+
+    fn timing(day: u8, part_1: Option<&str>, part_2: Option<&str>) -> Timing {
+        Timing {
+            day: crate::template::Day::new(day).unwrap(),
+            part_1: part_1.map(Into::into),
+            part_2: part_2.map(Into::into),
+            total_nanos: 0.0,
+            part_1_stats: None,
+            part_2_stats: None,
+            parse: None,
+            parse_stats: None,
+            failures: vec![],
+            memory: None,
+            parts: vec![],
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn parses_thresholds() {
+        assert_eq!("5%".parse(), Ok(Threshold::Percent(5.0)));
+        assert_eq!(
+            "2ms".parse(),
+            Ok(Threshold::Absolute(Duration::from_millis(2)))
+        );
+        assert_eq!(
+            "1.5µs".parse(),
+            Ok(Threshold::Absolute(Duration::from_nanos(1500)))
+        );
+        assert!("-5%".parse::<Threshold>().is_err());
+        assert!("fast".parse::<Threshold>().is_err());
+    }
+
+    #[test]
+    fn detects_regressions() {
+        let delta = PartDelta {
+            day: day!(1),
+            part: 1,
+            stored: Duration::from_millis(10),
+            current: Duration::from_millis(12),
+        };
+        assert_eq!(delta.percent(), 20.0);
+        assert!(delta.is_regression(Threshold::Percent(10.0)));
+        assert!(!delta.is_regression(Threshold::Percent(25.0)));
+        assert!(delta.is_regression(Threshold::Absolute(Duration::from_millis(1))));
+        assert!(!delta.is_regression(Threshold::Absolute(Duration::from_millis(2))));
+    }
+
+    #[test]
+    fn computes_speedups() {
+        let delta = |stored, current| PartDelta {
+            day: day!(1),
+            part: 1,
+            stored: Duration::from_millis(stored),
+            current: Duration::from_millis(current),
+        };
+        assert_eq!(delta(10, 4).speedup(), 2.5);
+        assert_eq!(delta(10, 20).speedup(), 0.5);
+        assert_eq!(delta(0, 0).speedup(), 1.0);
+    }
+
+    #[test]
+    fn compares_matching_parts() {
+        let stored = Timings {
+            data: vec![
+                timing(1, Some("10.0ms"), Some("1.0s")),
+                timing(2, Some("5.0µs"), None),
+            ],
+        };
+        let current = Timings {
+            data: vec![
+                timing(1, Some("11.0ms"), None),
+                timing(2, Some("4.0µs"), Some("1.0ms")),
+                timing(3, Some("1.0ms"), Some("1.0ms")),
+            ],
+        };
+
+        let deltas = compare(&stored, &current);
+        assert_eq!(deltas.len(), 2);
+        assert_eq!(deltas[0].day, day!(1));
+        assert_eq!(deltas[0].current, Duration::from_millis(11));
+        assert_eq!(deltas[1].day, day!(2));
+        assert_eq!(deltas[1].stored, Duration::from_micros(5));
+    }
+
+    #[test]
+    fn finds_missing_parts() {
+        let stored = Timings {
+            data: vec![
+                timing(1, Some("10.0ms"), Some("1.0s")),
+                timing(2, Some("5.0µs"), None),
+                timing(3, Some("1.0ms"), None),
+            ],
+        };
+        let mut failed = timing(1, Some("11.0ms"), None);
+        failed.failures = vec![(2, Failure::TimedOut(Duration::from_secs(1)))];
+        let current = Timings { data: vec![failed] };
+
+        // day 3 did not run, day 2 ran but did not report any result.
+        let days = HashSet::from([day!(1), day!(2)]);
+        assert_eq!(
+            missing(&stored, &current, &days),
+            vec![
+                MissingPart {
+                    day: day!(1),
+                    part: 2,
+                    stored: Duration::from_secs(1),
+                    failure: Some(Failure::TimedOut(Duration::from_secs(1))),
+                },
+                MissingPart {
+                    day: day!(2),
+                    part: 1,
+                    stored: Duration::from_micros(5),
+                    failure: None,
+                },
+            ]
+        );
+    }
+}
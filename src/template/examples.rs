@@ -0,0 +1,375 @@
+/// Manifest of the examples in `data/examples`, with their expected answers and parameters.
+/// The `solution!` macro generates a test per day that runs every example of the manifest.
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    path::PathBuf,
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    params,
+    registry::Solution,
+    results::{self, PartResult, PartStatus, PARSE_PART},
+    runner, Day, InputSource, PuzzleId, Year,
+};
+
+static EXAMPLES_FILE_NAME: &str = "examples.json";
+
+/// Represents a single example file, e.g. `12-21.txt`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Example {
+    pub file: String,
+    /// Expected answer of part 1. Parts without an expected answer are not checked.
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+    /// Parameters the solution needs to run this example, e.g. `size = 7`.
+    pub params: Vec<(String, String)>,
+}
+
+impl Example {
+    /// Day and input of the example, as given by its file name.
+    pub fn source(&self) -> Option<(Day, InputSource)> {
+        let stem = self.file.strip_suffix(".txt")?;
+
+        match stem.split_once('-') {
+            Some((day, suffix)) => Some((
+                day.parse().ok()?,
+                InputSource::Example(Some(suffix.parse().ok()?)),
+            )),
+            None => Some((stem.parse().ok()?, InputSource::Example(None))),
+        }
+    }
+
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Represents the examples of a year.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Examples {
+    pub data: Vec<Example>,
+}
+
+impl Examples {
+    /// Path of the examples file of a year.
+    pub fn file_path(year: Year) -> PathBuf {
+        year.data_dir().join(EXAMPLES_FILE_NAME)
+    }
+
+    /// Dehydrate examples to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(Examples::file_path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate examples from a JSON file. If not present, returns no examples.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        match fs::read_to_string(Examples::file_path(year)) {
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Examples::default()),
+            s => s.map_err(|x| x.to_string()).and_then(Examples::try_from),
+        }
+        .map_err(|e| {
+            format!(
+                "Failed to read \"{}\": {e}",
+                Examples::file_path(year).display()
+            )
+        })
+    }
+
+    /// Examples of a day, with their input.
+    pub fn for_day(&self, day: Day) -> impl Iterator<Item = (&Example, InputSource)> {
+        self.data
+            .iter()
+            .filter_map(move |example| match example.source() {
+                Some((d, input)) if d == day => Some((example, input)),
+                _ => None,
+            })
+    }
+}
+
+/// Run every example of a solution that has expected answers, and panic if any answer differs.
+/// Only the parts with an expected answer are run.
+pub fn check(solution: &Solution) {
+    let puzzle: PuzzleId = solution.puzzle;
+    let examples = Examples::read_from_file(puzzle.year).unwrap_or_else(|e| panic!("{e}"));
+
+    let mut mismatches = vec![];
+
+    for (example, input) in examples.for_day(puzzle.day) {
+        let parts: Vec<u8> = (1..=2).filter(|p| example.expected(*p).is_some()).collect();
+        if parts.is_empty() {
+            continue;
+        }
+
+        let input = input.read(puzzle);
+        let (_, results) = results::collect(|| {
+            params::with(&example.params, || {
+                runner::with_parts(&parts, || (solution.run)(puzzle, &input));
+            });
+        });
+
+        for part in parts {
+            let expected = example.expected(part).unwrap_or_default();
+            let actual = results.iter().find(|r| r.part == part);
+
+            match actual.and_then(|r| r.answer.as_deref()) {
+                Some(answer) if answer == expected => {}
+                Some(answer) => mismatches.push(format!(
+                    "{} part {part}: expected {expected}, got {answer}",
+                    example.file
+                )),
+                None => mismatches.push(format!(
+                    "{} part {part}: expected {expected}, {}",
+                    example.file,
+                    failure_of(&results, part)
+                )),
+            }
+        }
+    }
+
+    assert!(
+        mismatches.is_empty(),
+        "examples of {puzzle} failed:\n{}",
+        mismatches.join("\n")
+    );
+}
+
+/// Why a part produced no answer: its own failure, a failure of the parse step, or nothing at all.
+fn failure_of(results: &[PartResult], part: u8) -> String {
+    let failed = |part: u8| {
+        results
+            .iter()
+            .find(|r| r.part == part)
+            .and_then(|r| match &r.status {
+                PartStatus::Failed(failure) => Some(failure),
+                _ => None,
+            })
+    };
+
+    match (failed(part), failed(PARSE_PART)) {
+        (Some(failure), _) => format!("but it failed: {failure}"),
+        (None, Some(failure)) => format!("but parsing failed: {failure}"),
+        (None, None) => "got nothing".into(),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Examples> for JsonValue {
+    fn from(value: Examples) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Examples {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Examples {
+            data: json_data
+                .iter()
+                .map(Example::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Example> for JsonValue {
+    fn from(value: &Example) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("file".into(), JsonValue::String(value.file.clone()));
+
+        if let Some(part_1) = &value.part_1 {
+            map.insert("part_1".into(), JsonValue::String(part_1.clone()));
+        }
+
+        if let Some(part_2) = &value.part_2 {
+            map.insert("part_2".into(), JsonValue::String(part_2.clone()));
+        }
+
+        if !value.params.is_empty() {
+            map.insert(
+                "params".into(),
+                JsonValue::Object(
+                    value
+                        .params
+                        .iter()
+                        .map(|(k, v)| (k.clone(), JsonValue::String(v.clone())))
+                        .collect(),
+                ),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Example {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected example to be a JSON object.")?;
+
+        let file = json
+            .get("file")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected example.file to be a string.")?;
+
+        let part = |key: &str| {
+            json.get(key)
+                .map(|v| {
+                    v.get::<String>()
+                        .ok_or(format!("Expected example.{key} to be a string."))
+                })
+                .transpose()
+        };
+
+        // NOTE: parameters can be given as strings or numbers, they are parsed by the solution.
+        let mut params: Vec<(String, String)> = match json.get("params") {
+            None => vec![],
+            Some(v) => v
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("Expected example.params to be an object.")?
+                .iter()
+                .map(|(k, v)| match v {
+                    JsonValue::String(s) => Ok((k.clone(), s.clone())),
+                    JsonValue::Number(n) => Ok((k.clone(), n.to_string())),
+                    _ => Err(format!(
+                        "Expected example.params.{k} to be a string or number."
+                    )),
+                })
+                .collect::<Result<_, String>>()?,
+        };
+        params.sort();
+
+        Ok(Example {
+            file: file.clone(),
+            part_1: part("part_1")?.cloned(),
+            part_2: part("part_2")?.cloned(),
+            params,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{failure_of, Example, Examples};
+    use crate::{
+        day,
+        template::{
+            results::{Failure, PartResult, PartStatus, PARSE_PART},
+            InputSource,
+        },
+    };
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn parses_manifests() {
+        let examples = Examples::try_from(
+            r#"{"data":[
+                {"file":"12-21.txt","part_2":"80"},
+                {"file":"18.txt","part_1":"22","part_2":"6,1","params":{"size":7,"bytes":"12"}}
+            ]}"#
+            .to_string(),
+        )
+        .unwrap();
+
+        let (example, input) = examples.for_day(day!(12)).next().unwrap();
+        assert_eq!(input, InputSource::Example(Some(21)));
+        assert_eq!(example.expected(1), None);
+        assert_eq!(example.expected(2), Some("80"));
+
+        let (example, input) = examples.for_day(day!(18)).next().unwrap();
+        assert_eq!(input, InputSource::Example(None));
+        assert_eq!(
+            example.params,
+            vec![("bytes".into(), "12".into()), ("size".into(), "7".into())]
+        );
+
+        assert!(examples.for_day(day!(1)).next().is_none());
+    }
+
+    #[test]
+    fn roundtrips_examples() {
+        let example = Example {
+            file: "03-2.txt".into(),
+            part_1: None,
+            part_2: Some("48".into()),
+            params: vec![("size".into(), "7".into())],
+        };
+        let json = JsonValue::from(&example);
+        assert_eq!(Example::try_from(&json), Ok(example));
+    }
+
+    #[test]
+    fn ignores_unknown_file_names() {
+        let example = Example {
+            file: "notes.md".into(),
+            part_1: None,
+            part_2: None,
+            params: vec![],
+        };
+        assert_eq!(example.source(), None);
+    }
+
+    #[test]
+    fn explains_missing_answers() {
+        let failed = |part: u8, failure: Failure| PartResult {
+            part,
+            answer: None,
+            nanos: 0,
+            samples: 1,
+            stats: None,
+            memory: None,
+            status: PartStatus::Failed(failure),
+        };
+        let panic = || Failure::Panic {
+            message: "oops".into(),
+            location: None,
+        };
+
+        assert_eq!(failure_of(&[], 1), "got nothing");
+        assert_eq!(
+            failure_of(&[failed(1, panic())], 1),
+            "but it failed: panicked: oops"
+        );
+        assert_eq!(
+            failure_of(&[failed(PARSE_PART, panic())], 2),
+            "but parsing failed: panicked: oops"
+        );
+    }
+}
@@ -0,0 +1,189 @@
+/// Resource limits for solutions that run in their own process.
+/// The limits are passed to the solution binary through environment variables, which enforces them on itself:
+/// a watchdog thread ends the process once the timeout is reached, and the memory limit caps its address space.
+/// The parent kills a binary, and every process it started, if it is still running shortly after its timeout.
+use std::{
+    env,
+    process::{self, Command},
+    thread,
+    time::Duration,
+};
+
+use crate::template::{results::Failure, runner, timings::parse_duration};
+
+/// Environment variable that holds the timeout of a solution binary, e.g. `10s`.
+pub const TIMEOUT_ENV: &str = "AOC_TIMEOUT";
+/// Environment variable that holds the memory limit of a solution binary, e.g. `512M`.
+pub const MEMORY_LIMIT_ENV: &str = "AOC_MEMORY_LIMIT";
+
+/// Exit code of a solution binary that ran into its timeout, same as the one of coreutils' `timeout`.
+pub const TIMEOUT_EXIT_CODE: i32 = 124;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// Time a solution binary may run in total, i.e. all of its parts, including their benchmark samples
+    /// and the extra run that measures their heap usage.
+    pub timeout: Option<Duration>,
+    /// Maximum size of the address space in bytes. Only enforced on Linux.
+    pub memory: Option<u64>,
+}
+
+impl Limits {
+    pub fn is_set(&self) -> bool {
+        self.timeout.is_some() || self.memory.is_some()
+    }
+
+    /// Read the limits from `AOC_TIMEOUT` and `AOC_MEMORY_LIMIT`. Invalid values are ignored.
+    pub fn from_env() -> Self {
+        fn read<T>(key: &str, parse: fn(&str) -> Result<T, String>) -> Option<T> {
+            let value = env::var(key).ok()?;
+            parse(&value)
+                .map_err(|e| eprintln!("Ignoring {key}: {e}"))
+                .ok()
+        }
+
+        Limits {
+            timeout: read(TIMEOUT_ENV, Limits::parse_timeout),
+            memory: read(MEMORY_LIMIT_ENV, Limits::parse_memory),
+        }
+    }
+
+    /// Pass the limits to a child process.
+    pub fn apply_to(&self, command: &mut Command) {
+        if let Some(timeout) = self.timeout {
+            command.env(TIMEOUT_ENV, format!("{}ns", timeout.as_nanos()));
+        }
+
+        if let Some(memory) = self.memory {
+            command.env(MEMORY_LIMIT_ENV, memory.to_string());
+        }
+    }
+
+    /// Enforce the limits on the current process.
+    /// When the timeout is reached, the running part is reported as timed out and the process exits.
+    /// The watchdog is spawned before the address space is limited, as a small limit leaves no room for its stack.
+    pub fn enforce(&self) {
+        if let Some(timeout) = self.timeout {
+            thread::spawn(move || {
+                thread::sleep(timeout);
+                runner::report_interrupted(Failure::TimedOut(timeout));
+                process::exit(TIMEOUT_EXIT_CODE);
+            });
+        }
+
+        if let Some(memory) = self.memory {
+            if let Err(e) = limit_address_space(memory) {
+                eprintln!("Failed to limit memory: {e}");
+            }
+        }
+    }
+
+    /// Parse a timeout like `500ms` or `10s`.
+    pub fn parse_timeout(s: &str) -> Result<Duration, String> {
+        parse_duration(s)
+            .filter(|d| !d.is_zero())
+            .ok_or(format!("expected a duration like `10s`, got `{s}`."))
+    }
+
+    /// Parse a memory size like `512M` or `2GiB`. Suffixes are powers of 1024, plain numbers are bytes.
+    pub fn parse_memory(s: &str) -> Result<u64, String> {
+        let err = || format!("expected a size like `512M` or `2G`, got `{s}`.");
+
+        let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+        let (value, unit) = s.split_at(split);
+        let value: u64 = value.parse().map_err(|_| err())?;
+
+        let factor: u64 = match unit.trim().trim_end_matches("iB").trim_end_matches('B') {
+            "" => 1,
+            "K" | "k" => 1 << 10,
+            "M" | "m" => 1 << 20,
+            "G" | "g" => 1 << 30,
+            _ => return Err(err()),
+        };
+
+        value
+            .checked_mul(factor)
+            .filter(|bytes| *bytes > 0)
+            .ok_or_else(err)
+    }
+}
+
+/// Format a number of bytes with the largest fitting binary unit, e.g. `512.0 MiB`.
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    let units = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < units.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", units[unit])
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn limit_address_space(bytes: u64) -> Result<(), std::io::Error> {
+    let limit = libc::rlimit {
+        rlim_cur: bytes,
+        rlim_max: bytes,
+    };
+
+    // SAFETY: `limit` is a valid `rlimit` that outlives the call.
+    if unsafe { libc::setrlimit(libc::RLIMIT_AS, &limit) } == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn limit_address_space(_bytes: u64) -> Result<(), std::io::Error> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "memory limits are only supported on Linux.",
+    ))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, Limits};
+    use std::time::Duration;
+
+    #[test]
+    fn parses_timeouts() {
+        assert_eq!(Limits::parse_timeout("10s"), Ok(Duration::from_secs(10)));
+        assert_eq!(
+            Limits::parse_timeout("250ms"),
+            Ok(Duration::from_millis(250))
+        );
+        assert!(Limits::parse_timeout("0s").is_err());
+        assert!(Limits::parse_timeout("forever").is_err());
+    }
+
+    #[test]
+    fn parses_memory_sizes() {
+        assert_eq!(Limits::parse_memory("4096"), Ok(4096));
+        assert_eq!(Limits::parse_memory("64K"), Ok(64 * 1024));
+        assert_eq!(Limits::parse_memory("512M"), Ok(512 * 1024 * 1024));
+        assert_eq!(Limits::parse_memory("2GiB"), Ok(2 * 1024 * 1024 * 1024));
+        assert_eq!(Limits::parse_memory("1 GB"), Ok(1024 * 1024 * 1024));
+        assert!(Limits::parse_memory("0").is_err());
+        assert!(Limits::parse_memory("lots").is_err());
+        assert!(Limits::parse_memory("3T").is_err());
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(512 * 1024 * 1024), "512.0 MiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024 / 2), "1.5 GiB");
+    }
+}
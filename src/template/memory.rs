@@ -0,0 +1,198 @@
+/// Heap usage of solutions, measured by a global allocator that wraps the system allocator.
+/// The allocator is only installed with the `heap-stats` feature, so that other runs allocate without any overhead.
+/// Allocations are only counted while a part is measured, otherwise the allocator just forwards to the system one.
+/// With the `dhat-heap` feature, dhat is the global allocator and no heap usage is measured.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering},
+        Mutex,
+    },
+};
+use tinyjson::JsonValue;
+
+/// Whether heap usage is measured, i.e. whether the counting allocator is installed.
+pub const ENABLED: bool = cfg!(all(feature = "heap-stats", not(feature = "dhat-heap")));
+
+#[cfg(all(feature = "heap-stats", not(feature = "dhat-heap")))]
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+static MEASURING: AtomicBool = AtomicBool::new(false);
+/// Bytes allocated since the measurement started, negative if memory allocated before it was freed.
+static CURRENT: AtomicI64 = AtomicI64::new(0);
+static PEAK: AtomicI64 = AtomicI64::new(0);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+/// Only one measurement can run at a time, as the counters are shared by all threads.
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// Heap usage of a single run of a part.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Memory {
+    /// Most bytes that were allocated at the same time.
+    pub peak_bytes: u64,
+    pub allocations: u64,
+}
+
+pub struct CountingAlloc;
+
+#[allow(clippy::cast_possible_wrap)]
+fn record(size: usize, is_allocation: bool) {
+    let size = size as i64;
+    let delta = if is_allocation { size } else { -size };
+    let current = CURRENT.fetch_add(delta, Ordering::Relaxed) + delta;
+    PEAK.fetch_max(current, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() && MEASURING.load(Ordering::Relaxed) {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            record(layout.size(), true);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() && MEASURING.load(Ordering::Relaxed) {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            record(layout.size(), true);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        if MEASURING.load(Ordering::Relaxed) {
+            record(layout.size(), false);
+        }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() && MEASURING.load(Ordering::Relaxed) {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            record(layout.size(), false);
+            record(new_size, true);
+        }
+        new_ptr
+    }
+}
+
+/// Run `f` and measure its heap usage. Returns no usage if the counting allocator is not installed.
+/// Counting stops even if `f` panics, so that later measurements start from a clean state.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Memory>) {
+    struct Stop;
+
+    impl Drop for Stop {
+        fn drop(&mut self) {
+            MEASURING.store(false, Ordering::Relaxed);
+        }
+    }
+
+    if !ENABLED {
+        return (f(), None);
+    }
+
+    let _lock = LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+    CURRENT.store(0, Ordering::Relaxed);
+    PEAK.store(0, Ordering::Relaxed);
+    ALLOCATIONS.store(0, Ordering::Relaxed);
+    MEASURING.store(true, Ordering::Relaxed);
+
+    let stop = Stop;
+    let value = f();
+    drop(stop);
+
+    #[allow(clippy::cast_sign_loss)]
+    let memory = Memory {
+        peak_bytes: PEAK.load(Ordering::Relaxed).max(0) as u64,
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+    };
+
+    (value, Some(memory))
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Memory> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Memory) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert(
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
+        );
+        map.insert(
+            "allocations".into(),
+            JsonValue::Number(value.allocations as f64),
+        );
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Memory {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected memory to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|n| n as u64)
+                .ok_or(format!("Expected memory.{key} to be a number."))
+        };
+
+        Ok(Memory {
+            peak_bytes: number("peak_bytes")?,
+            allocations: number("allocations")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{measure, LOCK, MEASURING};
+    use std::{
+        hint::black_box,
+        panic,
+        sync::{atomic::Ordering, PoisonError},
+    };
+
+    #[test]
+    #[cfg_attr(not(feature = "heap-stats"), ignore = "needs the counting allocator")]
+    fn measures_heap_usage() {
+        let (_, memory) = measure(|| {
+            let small: Vec<u8> = black_box(Vec::with_capacity(1_000));
+            drop(small);
+            let large: Vec<u8> = black_box(Vec::with_capacity(4_000));
+            large.len()
+        });
+        let memory = memory.unwrap();
+
+        // NOTE: other tests may allocate at the same time, so the counts are lower bounds.
+        assert!(memory.allocations >= 2);
+        assert!(memory.peak_bytes >= 4_000);
+    }
+
+    #[test]
+    fn stops_counting_after_panics() {
+        let result = panic::catch_unwind(|| measure(|| panic!("part failed")));
+        assert!(result.is_err());
+
+        let _lock = LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        assert!(!MEASURING.load(Ordering::Relaxed));
+    }
+}
@@ -1,68 +1,210 @@
 use std::{env, fs};
 
 pub mod aoc_cli;
+pub mod client;
 pub mod commands;
+pub mod examples;
+pub mod params;
+pub mod registry;
 pub mod runner;
 
+pub use compare::Threshold;
 pub use day::*;
+pub use limits::Limits;
+pub use puzzle::*;
+pub use run_multi::RunMode;
+pub use stats::OutlierRule;
+pub use year::*;
 
+mod answers;
+mod aoc_http;
+mod bench_metadata;
+mod compare;
 mod day;
+mod limits;
+// NOTE: the counting allocator is only installed with `heap-stats`, and never alongside dhat.
+#[cfg_attr(
+    any(feature = "dhat-heap", not(feature = "heap-stats")),
+    allow(dead_code)
+)]
+mod memory;
+mod module_templates;
+mod progress;
+mod puzzle;
+mod puzzle_examples;
 mod readme_benchmarks;
+mod readme_stars;
+mod results;
 mod run_multi;
+mod stats;
+mod submissions;
+mod timing_history;
 mod timings;
+mod worktree;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
+/// A bare [`Day`] reads the file of the [`DEFAULT_YEAR`].
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, puzzle: impl Into<PuzzleId>) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd.join(puzzle.into().data_path(folder, None));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: impl Into<PuzzleId>, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
+    let filepath = cwd.join(puzzle.into().data_path(folder, Some(part)));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Input that a solution runs against.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input in `data/inputs`.
+    #[default]
+    Puzzle,
+    /// An example in `data/examples`, optionally with a suffix, e.g. `12-21.txt`.
+    Example(Option<u8>),
+}
+
+impl InputSource {
+    /// Read the source from the `--example [suffix]` arguments of the current process.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        match args.iter().position(|x| x == "--example") {
+            Some(i) => InputSource::Example(args.get(i + 1).and_then(|x| x.parse().ok())),
+            None => InputSource::Puzzle,
+        }
+    }
+
+    /// Arguments that select this source in a solution binary.
+    pub fn to_args(self) -> Vec<String> {
+        match self {
+            InputSource::Puzzle => vec![],
+            InputSource::Example(None) => vec!["--example".into()],
+            InputSource::Example(Some(suffix)) => vec!["--example".into(), suffix.to_string()],
+        }
+    }
+
+    #[must_use]
+    pub fn read(self, puzzle: PuzzleId) -> String {
+        match self {
+            InputSource::Puzzle => read_file("inputs", puzzle),
+            InputSource::Example(None) => read_file("examples", puzzle),
+            InputSource::Example(Some(suffix)) => read_file_part("examples", puzzle, suffix),
+        }
+    }
+}
+
+/// Creates the constants `DAY` and `PUZZLE` and sets up the input and runner for each part.
+/// The year of `PUZZLE` is taken from the binary name, e.g. `2023_01`, falling back to the [`DEFAULT_YEAR`].
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// An optional `parse = <function>` parameter parses the input once before running the parts.
+/// The parse step is timed separately, and each part receives a reference to the parsed input.
+///
+/// An optional `params { <name>: <type> = <default>, .. }` block declares parameters that differ between the examples
+/// and the real input, e.g. the size of a grid. They are read with the generated `params()` function, which falls back
+/// to the defaults unless the parameters are set by `--param <name>=<value>` or by an example in `data/examples.json`.
+///
+/// The macro also exposes a registry entry, which the main binary uses to run the solution in process,
+/// and a test that checks the answers of the examples listed in `data/examples.json`.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($day:expr $(, parse = $parse:expr)? $(, params { $($params:tt)* })?) => {
+        $crate::solution!(@impl $day, [$($parse)?], [$({ $($params)* })?], [part_one, 1] [part_two, 2]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    ($day:expr, 1 $(, parse = $parse:expr)? $(, params { $($params:tt)* })?) => {
+        $crate::solution!(@impl $day, [$($parse)?], [$({ $($params)* })?], [part_one, 1]);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    ($day:expr, 2 $(, parse = $parse:expr)? $(, params { $($params:tt)* })?) => {
+        $crate::solution!(@impl $day, [$($parse)?], [$({ $($params)* })?], [part_two, 2]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (
+        @impl $day:expr,
+        [$($parse:expr)?],
+        [$({ $($name:ident: $type:ty = $default:expr),* $(,)? })?],
+        $( [$func:expr, $part:expr] )*
+    ) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// The current puzzle.
+        const PUZZLE: $crate::template::PuzzleId =
+            $crate::template::PuzzleId::__for_bin(env!("CARGO_BIN_NAME"), DAY);
+
+        $(
+            /// Parameters of the current puzzle.
+            #[derive(Clone, Debug)]
+            struct Params {
+                $( $name: $type, )*
+            }
+
+            /// Read the parameters of the current run, falling back to their defaults.
+            fn params() -> Params {
+                Params {
+                    $( $name: $crate::template::params::get(stringify!($name)).unwrap_or($default), )*
+                }
+            }
+        )?
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        // Not part of the public API
+        #[doc(hidden)]
+        pub const __SOLUTION: $crate::template::registry::Solution =
+            $crate::template::registry::Solution {
+                puzzle: PUZZLE,
+                run: |puzzle, input| {
+                    use $crate::template::runner::*;
+                    $( let input = &run_parse($parse, input, puzzle); )?
+                    $( run_part(|input| $func(input), input, puzzle, $part); )*
+                },
+                params: &[$($(
+                    $crate::template::registry::Param {
+                        name: stringify!($name),
+                        ty: stringify!($type),
+                        parses: |value| value.parse::<$type>().is_ok(),
+                    }
+                ),*)?],
+            };
+
+        #[cfg(test)]
+        #[test]
+        fn example_manifest() {
+            $crate::template::examples::check(&__SOLUTION);
+        }
+
+        // NOTE: unused when the solution is compiled into the registry of the main binary.
+        #[allow(dead_code)]
         fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            $crate::template::Limits::from_env().enforce();
+            let source = $crate::template::InputSource::from_args();
+            let input = source.read(PUZZLE);
+
+            // NOTE: parameters passed as arguments override the ones of an example.
+            let args = $crate::template::params::from_args();
+            if let Err(e) = $crate::template::params::validate(&__SOLUTION, &args) {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+            $crate::template::params::run_input(PUZZLE, source, &args, || {
+                (__SOLUTION.run)(PUZZLE, &input)
+            });
         }
     };
 }
@@ -0,0 +1,115 @@
+/// Templates that `scaffold` creates solution modules from.
+/// The built-in templates are compiled in, user-defined templates are read from `templates/<name>.txt`
+/// in the project root and take precedence over built-in templates of the same name.
+use std::{fs, io::ErrorKind, path::PathBuf};
+
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+/// Directory of user-defined templates, relative to the project root.
+pub const TEMPLATES_DIR: &str = "templates";
+
+macro_rules! built_in {
+    ($name:literal) => {
+        (
+            $name,
+            include_str!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/src/templates/",
+                $name,
+                ".txt"
+            )),
+        )
+    };
+}
+
+const BUILT_IN: [(&str, &str); 5] = [
+    built_in!("default"),
+    built_in!("grid"),
+    built_in!("sections"),
+    built_in!("lines"),
+    built_in!("vm"),
+];
+
+fn user_template_path(name: &str) -> PathBuf {
+    PathBuf::from(TEMPLATES_DIR).join(format!("{name}.txt"))
+}
+
+/// Load a template by name, preferring a user-defined template over a built-in one.
+pub fn load(name: &str) -> Result<String, String> {
+    match fs::read_to_string(user_template_path(name)) {
+        Ok(template) => return Ok(template),
+        Err(e) if e.kind() != ErrorKind::NotFound => {
+            return Err(format!("could not read template `{name}`: {e}"));
+        }
+        Err(_) => {}
+    }
+
+    BUILT_IN
+        .iter()
+        .find(|(built_in, _)| *built_in == name)
+        .map(|(_, template)| template.to_string())
+        .ok_or_else(|| {
+            format!(
+                "unknown template `{name}`. Available templates: {}.",
+                names().join(", ")
+            )
+        })
+}
+
+/// Names of all available templates, built-in ones first.
+pub fn names() -> Vec<String> {
+    let mut names: Vec<String> = BUILT_IN.iter().map(|(name, _)| name.to_string()).collect();
+
+    let mut user_defined: Vec<String> = fs::read_dir(TEMPLATES_DIR)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+                .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+                .filter(|name| !names.contains(name))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    user_defined.sort();
+    names.append(&mut user_defined);
+    names
+}
+
+/// Replace the `%NAME%` placeholders of a template.
+pub fn render(template: &str, placeholders: &[(&str, String)]) -> String {
+    placeholders
+        .iter()
+        .fold(template.to_string(), |module, (name, value)| {
+            module.replace(&format!("%{name}%"), value)
+        })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{load, names, render};
+
+    #[test]
+    fn loads_built_in_templates() {
+        for name in ["default", "grid", "sections", "lines", "vm"] {
+            assert!(load(name).unwrap().contains("solution!(%DAY_NUMBER%)"));
+        }
+        assert!(names().starts_with(&["default".into(), "grid".into()]));
+        assert!(load("missing").unwrap_err().contains("default, grid"));
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let rendered = render(
+            "solution!(%DAY_NUMBER%); // %TITLE%, %UNKNOWN%",
+            &[
+                ("DAY_NUMBER", "7".into()),
+                ("TITLE", "Bridge Repair".into()),
+            ],
+        );
+        assert_eq!(rendered, "solution!(7); // Bridge Repair, %UNKNOWN%");
+    }
+}
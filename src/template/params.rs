@@ -0,0 +1,258 @@
+/// Named parameters of a solution, e.g. a grid size that differs between the examples and the real input.
+/// Solutions declare their parameters and defaults in `solution!`, which reads them from here.
+/// Parameters are set per thread, so that examples with different parameters can run side by side.
+use std::{cell::RefCell, env, str::FromStr};
+
+use crate::template::{examples::Examples, registry::Solution, runner, InputSource, PuzzleId};
+
+thread_local! {
+    static PARAMS: RefCell<Vec<(String, String)>> = const { RefCell::new(vec![]) };
+}
+
+/// Get a parameter of the running solution. Returns `None` if it is not set or can not be parsed.
+pub fn get<T: FromStr>(name: &str) -> Option<T> {
+    PARAMS.with_borrow(|params| {
+        params
+            .iter()
+            .rev()
+            .find(|(key, _)| key == name)
+            .and_then(|(_, value)| value.parse().ok())
+    })
+}
+
+/// Run `f` with the given parameters set on the current thread. The previous parameters are restored even if `f` panics.
+pub fn with<K: AsRef<str>, V: AsRef<str>, R>(params: &[(K, V)], f: impl FnOnce() -> R) -> R {
+    struct Restore(Vec<(String, String)>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            PARAMS.set(std::mem::take(&mut self.0));
+        }
+    }
+
+    let params = params
+        .iter()
+        .map(|(k, v)| (k.as_ref().to_string(), v.as_ref().to_string()))
+        .collect();
+
+    let _restore = Restore(PARAMS.replace(params));
+    f()
+}
+
+/// Parse a parameter like `size=7`.
+pub fn parse(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(format!("expected a parameter like `size=7`, got `{s}`.")),
+    }
+}
+
+/// Read the `--param name=value` arguments of the current process. Invalid parameters are ignored.
+pub fn from_args() -> Vec<(String, String)> {
+    let args: Vec<String> = env::args().collect();
+
+    args.windows(2)
+        .filter(|pair| pair[0] == "--param")
+        .filter_map(|pair| {
+            parse(&pair[1])
+                .map_err(|e| eprintln!("Ignoring parameter: {e}"))
+                .ok()
+        })
+        .collect()
+}
+
+/// Parts of an input that run with the same parameters. `None` runs all parts.
+type Run = (Option<Vec<u8>>, Vec<(String, String)>);
+
+/// Runs of an input with the parameters of its examples, as listed in `data/examples.json`.
+/// An example file that is listed more than once, e.g. with other parameters per part, runs once per entry with the
+/// parts that the entry has answers for. The real input always runs once with the defaults.
+fn runs(examples: &Examples, puzzle: PuzzleId, input: InputSource) -> Vec<Run> {
+    if input == InputSource::Puzzle {
+        return vec![(None, vec![])];
+    }
+
+    let entries: Vec<_> = examples
+        .for_day(puzzle.day)
+        .filter(|(_, source)| *source == input)
+        .map(|(example, _)| example)
+        .collect();
+
+    match entries.as_slice() {
+        [] => vec![(None, vec![])],
+        [example] => vec![(None, example.params.clone())],
+        entries => entries
+            .iter()
+            .map(|example| {
+                let parts: Vec<u8> = (1..=2).filter(|p| example.expected(*p).is_some()).collect();
+                let parts = (!parts.is_empty()).then_some(parts);
+                (parts, example.params.clone())
+            })
+            .collect(),
+    }
+}
+
+/// Run a solution on an input with the parameters of its examples. Parameters in `overrides` take precedence.
+pub fn run_input(
+    puzzle: PuzzleId,
+    input: InputSource,
+    overrides: &[(String, String)],
+    run: impl Fn(),
+) {
+    let examples = if input == InputSource::Puzzle {
+        Examples::default()
+    } else {
+        Examples::read_from_file(puzzle.year).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            Examples::default()
+        })
+    };
+
+    for (parts, mut params) in runs(&examples, puzzle, input) {
+        params.extend_from_slice(overrides);
+        with(&params, || match parts {
+            Some(parts) => runner::with_parts(&parts, &run),
+            None => run(),
+        });
+    }
+}
+
+/// Check parameters passed as arguments against the ones a solution declares.
+/// Errors on a parameter that is not declared or a value that does not parse, instead of running with the default.
+pub fn validate(solution: &Solution, params: &[(String, String)]) -> Result<(), String> {
+    for (name, value) in params {
+        let Some(param) = solution.params.iter().find(|p| p.name == name) else {
+            let declared: Vec<_> = solution
+                .params
+                .iter()
+                .map(|p| format!("`{}`", p.name))
+                .collect();
+
+            return Err(if declared.is_empty() {
+                format!(
+                    "{} does not declare any parameters, got `{name}`.",
+                    solution.puzzle
+                )
+            } else {
+                format!(
+                    "{} does not declare a parameter `{name}`, expected one of {}.",
+                    solution.puzzle,
+                    declared.join(", ")
+                )
+            });
+        };
+
+        if !(param.parses)(value) {
+            return Err(format!(
+                "parameter `{name}` expects a value of type `{}`, got `{value}`.",
+                param.ty
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{get, parse, runs, validate, with};
+    use crate::{
+        day,
+        template::{
+            examples::Examples,
+            registry::{Param, Solution},
+            InputSource, PuzzleId,
+        },
+    };
+    use std::panic;
+
+    #[test]
+    fn scopes_parameters() {
+        assert_eq!(get::<usize>("size"), None);
+
+        with(&[("size", "7"), ("name", "x")], || {
+            assert_eq!(get::<usize>("size"), Some(7));
+            assert_eq!(get::<usize>("name"), None);
+            assert_eq!(get::<String>("name"), Some("x".into()));
+        });
+
+        assert_eq!(get::<usize>("size"), None);
+    }
+
+    #[test]
+    fn restores_parameters_after_panics() {
+        let result = panic::catch_unwind(|| with(&[("size", "7")], || panic!("part failed")));
+
+        assert!(result.is_err());
+        assert_eq!(get::<usize>("size"), None);
+    }
+
+    #[test]
+    fn selects_parameters_per_part() {
+        let examples = Examples::try_from(
+            r#"{"data":[
+                {"file":"18.txt","part_1":"22","part_2":"6,1","params":{"size":7}},
+                {"file":"20.txt","part_1":"44","params":{"limit":1}},
+                {"file":"20.txt","part_2":"285","params":{"limit":50}}
+            ]}"#
+            .to_string(),
+        )
+        .unwrap();
+        let example = InputSource::Example(None);
+
+        assert_eq!(
+            runs(&examples, PuzzleId::from(day!(18)), example),
+            vec![(None, vec![("size".into(), "7".into())])]
+        );
+        assert_eq!(
+            runs(&examples, PuzzleId::from(day!(20)), example),
+            vec![
+                (Some(vec![1]), vec![("limit".into(), "1".into())]),
+                (Some(vec![2]), vec![("limit".into(), "50".into())]),
+            ]
+        );
+        assert_eq!(
+            runs(&examples, PuzzleId::from(day!(20)), InputSource::Puzzle),
+            vec![(None, vec![])]
+        );
+        assert_eq!(
+            runs(&examples, PuzzleId::from(day!(1)), example),
+            vec![(None, vec![])]
+        );
+    }
+
+    #[test]
+    fn validates_parameters() {
+        let solution = Solution {
+            puzzle: PuzzleId::from(day!(18)),
+            run: |_, _| {},
+            params: &[Param {
+                name: "size",
+                ty: "usize",
+                parses: |value| value.parse::<usize>().is_ok(),
+            }],
+        };
+        let param = |name: &str, value: &str| vec![(name.to_string(), value.to_string())];
+
+        assert_eq!(validate(&solution, &param("size", "7")), Ok(()));
+        assert_eq!(
+            validate(&solution, &param("size", "abc")),
+            Err("parameter `size` expects a value of type `usize`, got `abc`.".into())
+        );
+        assert!(validate(&solution, &param("sise", "7"))
+            .unwrap_err()
+            .contains("does not declare a parameter `sise`, expected one of `size`."));
+    }
+
+    #[test]
+    fn parses_parameters() {
+        assert_eq!(parse("size=7"), Ok(("size".into(), "7".into())));
+        assert_eq!(parse("name = a=b"), Ok(("name".into(), "a=b".into())));
+        assert!(parse("size").is_err());
+        assert!(parse("=7").is_err());
+    }
+}
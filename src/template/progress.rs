@@ -0,0 +1,316 @@
+/// Local record of the progress on each puzzle: when it was scaffolded and downloaded, and when each part was accepted.
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    path::PathBuf,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{submissions::Submissions, Day, PuzzleId, Year};
+
+static PROGRESS_FILE_NAME: &str = "progress.json";
+
+/// Puzzles unlock at midnight EST, which is 05:00 UTC.
+const UNLOCK_UTC_HOUR: u64 = 5;
+
+/// Seconds since the unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Time a puzzle unlocks, in seconds since the unix epoch.
+pub fn unlock_time(puzzle: PuzzleId) -> u64 {
+    // days since the epoch of a date in the proleptic gregorian calendar, see http://howardhinnant.github.io/date_algorithms.html
+    let (year, month, day) = (
+        u64::from(puzzle.year.into_inner()),
+        12,
+        u64::from(puzzle.day.into_inner()),
+    );
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month - 3) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    days * 86_400 + UNLOCK_UTC_HOUR * 3_600
+}
+
+/// Represents the progress on a single day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayProgress {
+    pub day: Day,
+    /// Title of the puzzle, e.g. `Day 1: Historian Hysteria`.
+    pub title: Option<String>,
+    /// Seconds since the unix epoch of the first scaffold.
+    pub scaffolded: Option<u64>,
+    /// Seconds since the unix epoch of the last download.
+    pub downloaded: Option<u64>,
+    /// Seconds since the unix epoch the answer of part 1 was accepted.
+    pub part_1: Option<u64>,
+    pub part_2: Option<u64>,
+}
+
+impl DayProgress {
+    fn new(day: Day) -> Self {
+        DayProgress {
+            day,
+            title: None,
+            scaffolded: None,
+            downloaded: None,
+            part_1: None,
+            part_2: None,
+        }
+    }
+
+    pub fn accepted(&self, part: u8) -> Option<u64> {
+        match part {
+            1 => self.part_1,
+            2 => self.part_2,
+            _ => None,
+        }
+    }
+
+    /// Title of the puzzle without its day, e.g. `Historian Hysteria`.
+    pub fn short_title(&self) -> Option<&str> {
+        let title = self.title.as_deref()?;
+        Some(title.split_once(": ").map_or(title, |(_, t)| t))
+    }
+}
+
+/// Represents the progress on every day of a year.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Progress {
+    pub data: Vec<DayProgress>,
+}
+
+impl Progress {
+    pub fn file_path(year: Year) -> PathBuf {
+        year.data_dir().join(PROGRESS_FILE_NAME)
+    }
+
+    /// Dehydrate progress to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(Progress::file_path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate progress from a JSON file. If not present, returns no progress.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        match fs::read_to_string(Progress::file_path(year)) {
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Progress::default()),
+            s => s.map_err(|x| x.to_string()).and_then(Progress::try_from),
+        }
+        .map_err(|e| {
+            format!(
+                "Failed to read \"{}\": {e}",
+                Progress::file_path(year).display()
+            )
+        })
+    }
+
+    /// Update the progress of a puzzle in its progress file.
+    /// A progress file that can not be read is left untouched, rather than replaced by the progress of this puzzle.
+    pub fn record(puzzle: PuzzleId, f: impl FnOnce(&mut DayProgress)) {
+        let mut progress = match Progress::read_from_file(puzzle.year) {
+            Ok(progress) => progress,
+            Err(e) => {
+                eprintln!("Failed to record progress: {e}");
+                return;
+            }
+        };
+        f(progress.entry(puzzle.day));
+
+        if let Err(e) = progress.store_file(puzzle.year) {
+            eprintln!("Failed to record progress: {e}");
+        }
+    }
+
+    pub fn get(&self, day: Day) -> Option<&DayProgress> {
+        self.data.iter().find(|p| p.day == day)
+    }
+
+    /// A part has a star if its answer was accepted, which may have been submitted before progress was tracked.
+    pub fn has_star(&self, submissions: &Submissions, day: Day, part: u8) -> bool {
+        self.get(day).and_then(|p| p.accepted(part)).is_some() || submissions.is_accepted(day, part)
+    }
+
+    /// Get the progress of a day, adding an empty entry if there is none.
+    pub fn entry(&mut self, day: Day) -> &mut DayProgress {
+        let index = match self.data.iter().position(|p| p.day == day) {
+            Some(index) => index,
+            None => {
+                self.data.push(DayProgress::new(day));
+                self.data.sort_unstable_by_key(|p| p.day);
+                self.data.iter().position(|p| p.day == day).unwrap()
+            }
+        };
+
+        &mut self.data[index]
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Progress> for JsonValue {
+    fn from(value: Progress) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Progress {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Progress {
+            data: json_data
+                .iter()
+                .map(DayProgress::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&DayProgress> for JsonValue {
+    fn from(value: &DayProgress) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+
+        #[allow(clippy::cast_precision_loss)]
+        let timestamp = |t: Option<u64>| t.map_or(JsonValue::Null, |t| JsonValue::Number(t as f64));
+
+        map.insert(
+            "title".into(),
+            value
+                .title
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("scaffolded".into(), timestamp(value.scaffolded));
+        map.insert("downloaded".into(), timestamp(value.downloaded));
+        map.insert("part_1".into(), timestamp(value.part_1));
+        map.insert("part_2".into(), timestamp(value.part_2));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for DayProgress {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected progress to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected progress.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(JsonValue::Number(n)) => Ok(Some(*n as u64)),
+            Some(_) => Err(format!("Expected progress.{key} to be null or a number.")),
+        };
+
+        let title = match json.get("title") {
+            None | Some(JsonValue::Null) => None,
+            Some(JsonValue::String(title)) => Some(title.clone()),
+            Some(_) => return Err("Expected progress.title to be null or string.".into()),
+        };
+
+        Ok(DayProgress {
+            day,
+            title,
+            scaffolded: timestamp("scaffolded")?,
+            downloaded: timestamp("downloaded")?,
+            part_1: timestamp("part_1")?,
+            part_2: timestamp("part_2")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{unlock_time, Progress};
+    use crate::{
+        day,
+        template::{aoc_cli::SubmissionOutcome, submissions::Submissions, PuzzleId, Year},
+    };
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn roundtrips_progress() {
+        let mut progress = Progress::default();
+        progress.entry(day!(3)).part_1 = Some(1_733_205_600);
+        progress.entry(day!(1)).title = Some("Day 1: Historian Hysteria".into());
+
+        let json = JsonValue::from(progress.clone()).stringify().unwrap();
+        let parsed = Progress::try_from(json).unwrap();
+
+        assert_eq!(parsed.data, progress.data);
+        assert_eq!(parsed.data[0].day, day!(1));
+        assert_eq!(
+            parsed.get(day!(3)).unwrap().accepted(1),
+            Some(1_733_205_600)
+        );
+        assert_eq!(parsed.get(day!(3)).unwrap().accepted(2), None);
+    }
+
+    #[test]
+    fn counts_accepted_parts_as_stars() {
+        let mut progress = Progress::default();
+        progress.entry(day!(1)).part_1 = Some(1_733_029_800);
+
+        let mut submissions = Submissions::default();
+        submissions.record(day!(2), 1, "2", SubmissionOutcome::Correct);
+        submissions.record(day!(2), 2, "20", SubmissionOutcome::TooLow(None));
+
+        assert!(progress.has_star(&submissions, day!(1), 1));
+        assert!(!progress.has_star(&submissions, day!(1), 2));
+        assert!(progress.has_star(&submissions, day!(2), 1));
+        assert!(!progress.has_star(&submissions, day!(2), 2));
+    }
+
+    #[test]
+    fn computes_unlock_times() {
+        // 2024-12-01T05:00:00Z
+        let puzzle = PuzzleId::new(Year::new(2024).unwrap(), day!(1));
+        assert_eq!(unlock_time(puzzle), 1_733_029_200);
+
+        // 2015-12-25T05:00:00Z
+        let puzzle = PuzzleId::new(Year::new(2015).unwrap(), day!(25));
+        assert_eq!(unlock_time(puzzle), 1_451_019_600);
+    }
+}
@@ -0,0 +1,124 @@
+use std::fmt::Display;
+use std::path::PathBuf;
+
+use crate::template::{Day, Year, DEFAULT_YEAR};
+
+/// Identifies a puzzle by its year and day.
+///
+/// # Display
+/// This value displays as `year/day`.
+///
+/// ```
+/// # use advent_of_code::template::{Day, PuzzleId, Year};
+/// let puzzle = PuzzleId::new(Year::new(2023).unwrap(), Day::new(8).unwrap());
+/// assert_eq!(puzzle.to_string(), "2023/08");
+/// assert_eq!(puzzle.bin_name(), "2023_08");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    // Not part of the public API
+    /// Resolve the puzzle of a binary: `2023_01` belongs to 2023, `01` to the [`DEFAULT_YEAR`].
+    #[doc(hidden)]
+    pub const fn __for_bin(bin_name: &str, day: Day) -> Self {
+        let year = match bin_name.as_bytes() {
+            [a, b, c, d, b'_', ..] => Year::__parse(&[*a, *b, *c, *d]),
+            _ => DEFAULT_YEAR,
+        };
+
+        match year {
+            Some(year) => Self::new(year, day),
+            None => panic!("AOC_YEAR must be set to a valid year in `.cargo/config.toml`."),
+        }
+    }
+
+    /// Name of the binary that solves this puzzle.
+    pub fn bin_name(self) -> String {
+        if self.year.is_default() {
+            self.day.to_string()
+        } else {
+            format!("{}_{}", self.year, self.day)
+        }
+    }
+
+    /// Path of the module that solves this puzzle.
+    pub fn bin_path(self) -> String {
+        format!("./src/bin/{}.rs", self.bin_name())
+    }
+
+    /// Path of this puzzle's file in a data folder (`inputs`, `examples`, ...).
+    /// The optional suffix selects alternative files, e.g. `01-2.txt`.
+    pub fn data_path(self, folder: &str, suffix: Option<u8>) -> PathBuf {
+        let file_name = match suffix {
+            Some(suffix) => format!("{}-{suffix}.txt", self.day),
+            None => format!("{}.txt", self.day),
+        };
+        self.year.data_dir().join(folder).join(file_name)
+    }
+
+    /// Path of the puzzle description.
+    pub fn puzzle_path(self) -> PathBuf {
+        self.year
+            .data_dir()
+            .join("puzzles")
+            .join(format!("{}.md", self.day))
+    }
+}
+
+/// Puzzles given by day only belong to the [`DEFAULT_YEAR`].
+impl From<Day> for PuzzleId {
+    fn from(day: Day) -> Self {
+        Self::__for_bin("", day)
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.year, self.day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::PuzzleId;
+    use crate::{day, template::Year};
+    use std::path::PathBuf;
+
+    #[test]
+    fn scopes_other_years() {
+        let puzzle = PuzzleId::__for_bin("2016_03", day!(3));
+        assert_eq!(puzzle.year, Year::new(2016).unwrap());
+        assert_eq!(puzzle.bin_name(), "2016_03");
+        assert_eq!(puzzle.bin_path(), "./src/bin/2016_03.rs");
+        assert_eq!(
+            puzzle.data_path("examples", Some(2)),
+            PathBuf::from("data/2016/examples/03-2.txt")
+        );
+        assert_eq!(
+            puzzle.puzzle_path(),
+            PathBuf::from("data/2016/puzzles/03.md")
+        );
+    }
+
+    #[test]
+    fn uses_flat_layout_for_default_year() {
+        let puzzle = PuzzleId::from(day!(3));
+        assert!(puzzle.year.is_default());
+        assert_eq!(PuzzleId::__for_bin("03", day!(3)), puzzle);
+        assert_eq!(puzzle.bin_name(), "03");
+        assert_eq!(
+            puzzle.data_path("inputs", None),
+            PathBuf::from("data/inputs/03.txt")
+        );
+    }
+}
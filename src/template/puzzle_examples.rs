@@ -0,0 +1,244 @@
+/// Extracts example inputs and their expected answers from a puzzle description in markdown.
+/// Examples are the code blocks introduced by a paragraph that mentions an example, answers are the emphasised
+/// code spans, e.g. `*11*`. The last answer after an example is assumed to be its expected answer.
+///
+/// An example input found in a puzzle description.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PuzzleExample {
+    pub input: String,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+enum Block<'a> {
+    Text(&'a str),
+    Code(String),
+}
+
+/// Find the distinct example inputs of a puzzle, in the order they appear in.
+pub fn extract(markdown: &str) -> Vec<PuzzleExample> {
+    let mut examples: Vec<PuzzleExample> = vec![];
+
+    for (index, section) in split_parts(markdown).into_iter().enumerate() {
+        let blocks = split_blocks(section);
+
+        // NOTE: some puzzles introduce their first example without calling it one.
+        let fallback = if examples.is_empty() && !(0..blocks.len()).any(|i| is_example(&blocks, i))
+        {
+            blocks
+                .iter()
+                .position(|block| matches!(block, Block::Code(input) if input.lines().count() > 1))
+        } else {
+            None
+        };
+
+        // text before the first example of a part usually refers back to the examples of part one.
+        let mut current = examples.len().checked_sub(1);
+
+        for (i, block) in blocks.iter().enumerate() {
+            match block {
+                Block::Code(input) if is_example(&blocks, i) || fallback == Some(i) => {
+                    current = Some(add_example(&mut examples, input));
+                }
+                Block::Code(_) => {}
+                Block::Text(text) => {
+                    if let (Some(answer), Some(current)) = (find_answers(text).pop(), current) {
+                        let example = &mut examples[current];
+                        match index {
+                            0 => example.part_1 = Some(answer),
+                            _ => example.part_2 = Some(answer),
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    examples
+}
+
+/// A code block is an example if the paragraph before it mentions one, e.g. "For example:".
+fn is_example(blocks: &[Block], i: usize) -> bool {
+    match (&blocks[i], i.checked_sub(1).map(|i| &blocks[i])) {
+        (Block::Code(_), Some(Block::Text(text))) => {
+            last_paragraph(text).to_lowercase().contains("example")
+        }
+        _ => false,
+    }
+}
+
+/// Return the index of an example with the given input, adding it if it was not seen before.
+fn add_example(examples: &mut Vec<PuzzleExample>, input: &str) -> usize {
+    match examples.iter().position(|e| e.input == input) {
+        Some(i) => i,
+        None => {
+            examples.push(PuzzleExample {
+                input: input.to_string(),
+                ..Default::default()
+            });
+            examples.len() - 1
+        }
+    }
+}
+
+/// Split the description into part one and, if unlocked, part two.
+fn split_parts(markdown: &str) -> Vec<&str> {
+    let heading = markdown.match_indices("## ").map(|(i, _)| i).find(|i| {
+        markdown[*i..]
+            .lines()
+            .next()
+            .is_some_and(|line| line.contains("Part Two"))
+    });
+
+    match heading {
+        Some(i) => vec![&markdown[..i], &markdown[i..]],
+        None => vec![markdown],
+    }
+}
+
+/// Split a section into text and fenced code blocks.
+fn split_blocks(section: &str) -> Vec<Block<'_>> {
+    let mut blocks = vec![];
+    let mut rest = section;
+
+    while let Some(start) = rest.find("```") {
+        blocks.push(Block::Text(&rest[..start]));
+
+        let after_fence = &rest[start + 3..];
+        // skip the language of the fence, if any.
+        let body_start = after_fence.find('\n').map_or(after_fence.len(), |i| i + 1);
+        let body = &after_fence[body_start..];
+
+        let Some(end) = body.find("```") else {
+            rest = "";
+            break;
+        };
+
+        let input = body[..end].trim_end_matches('\n');
+        blocks.push(Block::Code(format!("{input}\n")));
+        rest = &body[end + 3..];
+    }
+
+    blocks.push(Block::Text(rest));
+    blocks
+}
+
+fn last_paragraph(text: &str) -> &str {
+    let text = text.trim_end();
+    text.rsplit_once("\n\n").map_or(text, |(_, p)| p)
+}
+
+/// Find emphasised code spans, written as `` `*11*` `` or `` *`11`* ``.
+fn find_answers(text: &str) -> Vec<String> {
+    let mut answers = vec![];
+    let mut rest = text;
+
+    while let Some(start) = rest.find(['`', '*']) {
+        let close = match &rest[start..] {
+            s if s.starts_with("`*") => "*`",
+            s if s.starts_with("*`") => "`*",
+            _ => {
+                rest = &rest[start + 1..];
+                continue;
+            }
+        };
+
+        let after = &rest[start + 2..];
+        let Some(end) = after.find(close) else {
+            break;
+        };
+
+        let answer = &after[..end];
+        if !answer.is_empty() && !answer.contains('\n') {
+            answers.push(answer.to_string());
+        }
+        rest = &after[end + 2..];
+    }
+
+    answers
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extract, PuzzleExample};
+
+    const PUZZLE: &str = "## --- Day 1: Historian Hysteria ---
+
+Throughout the Chief's office, the historically significant locations are listed.
+
+For example:
+
+```
+3   4
+4   3
+```
+
+Pair up the smallest numbers, a distance of `*2*`.
+
+To find the *total distance*, add up all of the distances. In the example above, this is `*11*`!
+
+## --- Part Two ---
+
+Here are the same example lists again:
+
+```
+3   4
+4   3
+```
+
+So, for these example lists, the similarity score at the end of this process is `*31*`.
+
+For a second example, consider:
+
+```
+1 2
+```
+
+This one has a score of *`3`* and *in total* `*7*`.";
+
+    #[test]
+    fn extracts_examples_and_answers() {
+        assert_eq!(
+            extract(PUZZLE),
+            vec![
+                PuzzleExample {
+                    input: "3   4\n4   3\n".into(),
+                    part_1: Some("11".into()),
+                    part_2: Some("31".into()),
+                },
+                PuzzleExample {
+                    input: "1 2\n".into(),
+                    part_1: None,
+                    part_2: Some("7".into()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn assigns_answers_to_earlier_examples() {
+        let puzzle = PUZZLE.split("For a second example").next().unwrap();
+        let examples = extract(puzzle);
+
+        assert_eq!(examples.len(), 1);
+        assert_eq!(examples[0].part_1.as_deref(), Some("11"));
+        assert_eq!(examples[0].part_2.as_deref(), Some("31"));
+    }
+
+    #[test]
+    fn falls_back_to_the_first_block() {
+        let puzzle = "Consider this map:\n\n```\n..#\n#..\n```\n\nIt has `*2*` walls.";
+        let examples = extract(puzzle);
+
+        assert_eq!(examples.len(), 1);
+        assert_eq!(examples[0].input, "..#\n#..\n");
+        assert_eq!(examples[0].part_1.as_deref(), Some("2"));
+    }
+
+    #[test]
+    fn handles_descriptions_without_examples() {
+        assert!(extract("## --- Day 25 ---\n\nNo code here, only `*text*`.").is_empty());
+    }
+}
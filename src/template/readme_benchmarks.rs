@@ -1,9 +1,11 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{env, fs, io, str::FromStr};
 
+use crate::template::compare::PartDelta;
+use crate::template::limits::format_bytes;
 use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::{PuzzleId, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -21,17 +23,26 @@ impl From<std::io::Error> for Error {
 }
 
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub pos_start: usize,
+    pub pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// Every year has its own table, the default year uses the plain marker.
+pub fn year_marker(marker: &str, year: Year) -> String {
+    if year.is_default() {
+        marker.into()
+    } else {
+        marker.replace(" --->", &format!(" {year} --->"))
+    }
+}
+
+fn get_marker(year: Year) -> String {
+    year_marker(MARKER, year)
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Find the table between two occurences of a marker. A single marker is replaced by the table.
+pub fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,55 +63,244 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
-
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
-
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+/// Environment variable that selects the optional columns of the table, e.g. `parse,heap,bar`.
+pub const COLUMNS_ENV: &str = "AOC_README_COLUMNS";
+
+/// Optional column of the table. A column is only shown if at least one day has data for it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    /// Duration of the parse step.
+    Parse,
+    /// Mean next to the median duration of each part.
+    Mean,
+    /// Highest heap usage of the parts.
+    Heap,
+    /// Number of allocations of all parts.
+    Allocations,
+    /// Share of the total duration, as a proportional bar.
+    Bar,
+    /// Parts that got slower than the stored timings in this run.
+    Regression,
+}
+
+impl Column {
+    /// Read the columns from `AOC_README_COLUMNS`. Only the parse step is shown by default.
+    pub fn from_env() -> Vec<Self> {
+        match env::var(COLUMNS_ENV) {
+            Ok(s) => s
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .filter_map(|s| {
+                    s.parse()
+                        .map_err(|e| eprintln!("Ignoring column in {COLUMNS_ENV}: {e}"))
+                        .ok()
+                })
+                .collect(),
+            Err(_) => vec![Column::Parse],
+        }
+    }
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Column::Parse),
+            "mean" => Ok(Column::Mean),
+            "heap" => Ok(Column::Heap),
+            "allocations" => Ok(Column::Allocations),
+            "bar" => Ok(Column::Bar),
+            "regression" => Ok(Column::Regression),
+            x => Err(format!(
+                "unknown column `{x}`, expecting `parse`, `mean`, `heap`, `allocations`, `bar` or `regression`."
+            )),
+        }
+    }
+}
+
+const BAR_WIDTH: f64 = 20.0;
+const EIGHTHS: [&str; 8] = ["", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+
+/// A bar of `share` times the full width, with eighth blocks for the remainder.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn bar(share: f64) -> String {
+    let eighths = (share.clamp(0.0, 1.0) * BAR_WIDTH * 8.0).round() as usize;
+    "█".repeat(eighths / 8) + EIGHTHS[eighths % 8]
+}
+
+fn construct_table(
+    prefix: &str,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+    columns: &[Column],
+    regressions: &[PartDelta],
+) -> String {
+    let marker = get_marker(year);
+    let header = if year.is_default() {
+        format!("{prefix} Benchmarks")
+    } else {
+        format!("{prefix} {year} Benchmarks")
+    };
+
+    // optional columns are only shown once a day has data for them.
+    let is_shown = |column: Column| {
+        columns.contains(&column)
+            && match column {
+                Column::Parse => timings.data.iter().any(|t| t.parse.is_some()),
+                Column::Mean => timings
+                    .data
+                    .iter()
+                    .any(|t| t.part_1_stats.is_some() || t.part_2_stats.is_some()),
+                Column::Heap | Column::Allocations => {
+                    timings.data.iter().any(|t| t.memory.is_some())
+                }
+                Column::Bar => total_millis > 0.0,
+                Column::Regression => !regressions.is_empty(),
+            }
+    };
+
+    let has_mean = is_shown(Column::Mean);
+    let part_header = |part: u8| {
+        if has_mean {
+            format!("Part {part} (median / mean)")
+        } else {
+            format!("Part {part}")
+        }
+    };
+
+    let mut headers = vec!["Day".to_string()];
+    if is_shown(Column::Parse) {
+        headers.push("Parse".into());
+    }
+    headers.push(part_header(1));
+    headers.push(part_header(2));
+    for (column, name) in [
+        (Column::Heap, "Peak heap"),
+        (Column::Allocations, "Allocations"),
+        (Column::Bar, "Share"),
+        (Column::Regression, "Regression"),
+    ] {
+        if is_shown(column) {
+            headers.push(name.into());
+        }
+    }
+
+    let mut lines: Vec<String> = vec![marker.clone(), header, String::new()];
+    lines.push(format!("| {} |", headers.join(" | ")));
+    lines.push(format!("| {}  |", vec![":---:"; headers.len()].join(" | ")));
+
+    for timing in &timings.data {
+        let path = PuzzleId::new(year, timing.day).bin_path();
+        let mut cells = vec![format!("[Day {}]({})", timing.day.into_inner(), path)];
+
+        if is_shown(Column::Parse) {
+            cells.push(format!("`{}`", timing.parse.as_deref().unwrap_or("-")));
+        }
+
+        for (median, stats) in [
+            (&timing.part_1, &timing.part_1_stats),
+            (&timing.part_2, &timing.part_2_stats),
+        ] {
+            let median = format!("`{}`", median.as_deref().unwrap_or("-"));
+            cells.push(match stats {
+                Some(stats) if has_mean => format!("{median} / `{:.1?}`", stats.mean),
+                _ => median,
+            });
+        }
+
+        if is_shown(Column::Heap) {
+            cells.push(
+                timing
+                    .memory
+                    .map_or("-".into(), |m| format_bytes(m.peak_bytes)),
+            );
+        }
+
+        if is_shown(Column::Allocations) {
+            cells.push(
+                timing
+                    .memory
+                    .map_or("-".into(), |m| m.allocations.to_string()),
+            );
+        }
+
+        if is_shown(Column::Bar) {
+            let share = timing.total_nanos / 1_000_000_f64 / total_millis;
+            cells.push(
+                format!("{} {:.1}%", bar(share), share * 100.0)
+                    .trim()
+                    .to_string(),
+            );
+        }
+
+        if is_shown(Column::Regression) {
+            let regressed: Vec<String> = regressions
+                .iter()
+                .filter(|delta| delta.day == timing.day)
+                .map(|delta| match delta.part {
+                    0 => format!("🔺 parse {:+.1}%", delta.percent()),
+                    part => format!("🔺 part {part} {:+.1}%", delta.percent()),
+                })
+                .collect();
+            cells.push(regressed.join(", "));
+        }
+
+        lines.push(format!("| {} |", cells.join(" | ")));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker);
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(
+    s: &mut String,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+    columns: &[Column],
+    regressions: &[PartDelta],
+) -> Result<(), Error> {
+    let positions = locate_table(s, &get_marker(year))?;
+    let table = construct_table("##", year, timings, total_millis, columns, regressions);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+/// Update the table of a year. The `regressions` of the latest run are marked if the column is selected.
+pub fn update(year: Year, timings: Timings, regressions: &[PartDelta]) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(
+        &mut readme,
+        year,
+        timings,
+        total_millis,
+        &Column::from_env(),
+        regressions,
+    )?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{bar, update_content, Column, MARKER};
+    use crate::{
+        day,
+        template::compare::PartDelta,
+        template::memory::Memory,
+        template::stats::{OutlierRule, Stats},
+        template::timings::{Timing, Timings},
+        template::{Year, DEFAULT_YEAR},
+    };
+    use std::time::Duration;
 
     fn get_mock_timings() -> Timings {
         Timings {
@@ -110,18 +310,42 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    failures: vec![],
+                    memory: None,
+                    parts: vec![],
+                    metadata: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    failures: vec![],
+                    memory: None,
+                    parts: vec![],
+                    metadata: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    failures: vec![],
+                    memory: None,
+                    parts: vec![],
+                    metadata: None,
                 },
             ],
         }
@@ -131,28 +355,68 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            DEFAULT_YEAR.unwrap(),
+            get_mock_timings(),
+            190.0,
+            &[Column::Parse],
+            &[],
+        )
+        .unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            DEFAULT_YEAR.unwrap(),
+            get_mock_timings(),
+            190.0,
+            &[Column::Parse],
+            &[],
+        )
+        .unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            DEFAULT_YEAR.unwrap(),
+            get_mock_timings(),
+            190.0,
+            &[Column::Parse],
+            &[],
+        )
+        .unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            DEFAULT_YEAR.unwrap(),
+            get_mock_timings(),
+            190.0,
+            &[Column::Parse],
+            &[],
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            DEFAULT_YEAR.unwrap(),
+            get_mock_timings(),
+            190.0,
+            &[Column::Parse],
+            &[],
+        )
+        .unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +424,15 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            DEFAULT_YEAR.unwrap(),
+            get_mock_timings(),
+            190.0,
+            &[Column::Parse],
+            &[],
+        )
+        .unwrap();
         let expected = [
             "foo",
             "bar",
@@ -180,4 +452,108 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn keeps_tables_per_year() {
+        let year = Year::new(2016).unwrap();
+        let marker = "<!--- benchmarking table 2016 --->";
+        let mut s = format!("{MARKER}{MARKER}\n{marker}{marker}");
+        update_content(
+            &mut s,
+            year,
+            get_mock_timings(),
+            190.0,
+            &[Column::Parse],
+            &[],
+        )
+        .unwrap();
+        assert!(s.starts_with(&format!("{MARKER}{MARKER}\n{marker}\n## 2016 Benchmarks")));
+        assert!(s.contains("| [Day 1](./src/bin/2016_01.rs) | `10ms` | `20ms` |"));
+        assert!(update_content(
+            &mut s,
+            DEFAULT_YEAR.unwrap(),
+            get_mock_timings(),
+            190.0,
+            &[Column::Parse],
+            &[]
+        )
+        .is_ok());
+        assert_eq!(s.matches("## 2016 Benchmarks").count(), 1);
+    }
+
+    #[test]
+    fn adds_parse_column() {
+        let mut timings = get_mock_timings();
+        timings.data[1].parse = Some("5ms".into());
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            DEFAULT_YEAR.unwrap(),
+            timings,
+            190.0,
+            &[Column::Parse],
+            &[],
+        )
+        .unwrap();
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 |"));
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `-` | `10ms` | `20ms` |"));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `5ms` | `30ms` | `40ms` |"));
+    }
+
+    #[test]
+    fn draws_proportional_bars() {
+        assert_eq!(bar(0.0), "");
+        assert_eq!(bar(0.5), "██████████");
+        assert_eq!(bar(0.158), "███▏");
+        assert_eq!(bar(1.5), "█".repeat(20));
+    }
+
+    #[test]
+    fn adds_configured_columns() {
+        let mut timings = get_mock_timings();
+        timings.data[0].memory = Some(Memory {
+            peak_bytes: 2 * 1024 * 1024,
+            allocations: 42,
+        });
+        timings.data[0].part_1_stats = Stats::from_samples(
+            &[Duration::from_millis(10), Duration::from_millis(12)],
+            OutlierRule::Keep,
+        );
+        let total_millis = timings.total_millis();
+        let regressions = [PartDelta {
+            day: day!(2),
+            part: 2,
+            stored: Duration::from_millis(20),
+            current: Duration::from_millis(40),
+        }];
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            DEFAULT_YEAR.unwrap(),
+            timings,
+            total_millis,
+            &[
+                Column::Parse,
+                Column::Mean,
+                Column::Heap,
+                Column::Allocations,
+                Column::Bar,
+                Column::Regression,
+            ],
+            &regressions,
+        )
+        .unwrap();
+
+        // the parse column is not shown without data for it.
+        assert!(s.contains(
+            "| Day | Part 1 (median / mean) | Part 2 (median / mean) | Peak heap | Allocations | Share | Regression |"
+        ));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/01.rs) | `10ms` / `11.0ms` | `20ms` | 2.0 MiB | 42 | ███▏ 15.8% |  |"
+        ));
+        assert!(s.contains(
+            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | - | - | ███████▍ 36.8% | 🔺 part 2 +100.0% |"
+        ));
+    }
 }
@@ -0,0 +1,158 @@
+/// Module that updates the readme with the stars of each day, from the locally recorded progress.
+/// It writes the same table as `aoc-readme-stars`, without having to query the Advent of Code API.
+use std::fs;
+
+use crate::template::progress::Progress;
+use crate::template::readme_benchmarks::{locate_table, year_marker, Error};
+use crate::template::submissions::Submissions;
+use crate::template::{all_days, Year};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+fn construct_table(
+    prefix: &str,
+    year: Year,
+    progress: &Progress,
+    submissions: &Submissions,
+) -> String {
+    let marker = year_marker(MARKER, year);
+
+    let mut lines: Vec<String> = vec![
+        marker.clone(),
+        format!("{prefix} {year} Results"),
+        String::new(),
+        "| Day | Puzzle | Part 1 | Part 2 |".into(),
+        "| :---: | :--- | :---: | :---: |".into(),
+    ];
+
+    let mut total = 0;
+
+    for day in all_days() {
+        let stars = [1, 2].map(|part| progress.has_star(submissions, day, part));
+        if !stars.contains(&true) {
+            continue;
+        }
+
+        total += stars.iter().filter(|s| **s).count();
+
+        let title = progress
+            .get(day)
+            .and_then(|p| p.short_title())
+            .map_or_else(|| format!("Day {}", day.into_inner()), str::to_string);
+        let star = |s: bool| if s { "⭐" } else { " " };
+
+        lines.push(format!(
+            "| {} | [{title}](https://adventofcode.com/{year}/day/{}) | {} | {} |",
+            day.into_inner(),
+            day.into_inner(),
+            star(stars[0]),
+            star(stars[1]),
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(format!("**Total: {total} ⭐**"));
+    lines.push(marker);
+
+    lines.join("\n")
+}
+
+fn update_content(
+    s: &mut String,
+    year: Year,
+    progress: &Progress,
+    submissions: &Submissions,
+) -> Result<(), Error> {
+    let positions = locate_table(s, &year_marker(MARKER, year))?;
+    let table = construct_table("##", year, progress, submissions);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+/// Update the stars table of a year. Returns `false` without touching the readme if no star was recorded locally,
+/// so that a table filled by the GitHub action is kept.
+pub fn update(year: Year) -> Result<bool, Error> {
+    let progress = Progress::read_from_file(year).map_err(Error::Parser)?;
+    let submissions = Submissions::read_from_file(year).map_err(Error::Parser)?;
+
+    let has_stars = all_days().any(|day| {
+        progress.has_star(&submissions, day, 1) || progress.has_star(&submissions, day, 2)
+    });
+    if !has_stars {
+        return Ok(false);
+    }
+
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, year, &progress, &submissions)?;
+    fs::write(path, &readme)?;
+    Ok(true)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{update_content, MARKER};
+    use crate::{
+        day,
+        template::{
+            aoc_cli::SubmissionOutcome, progress::Progress, submissions::Submissions, Year,
+            DEFAULT_YEAR,
+        },
+    };
+
+    fn get_mock_progress() -> (Progress, Submissions) {
+        let mut progress = Progress::default();
+        let entry = progress.entry(day!(1));
+        entry.title = Some("Day 1: Historian Hysteria".into());
+        entry.part_1 = Some(1_733_029_800);
+        entry.part_2 = Some(1_733_040_000);
+        progress.entry(day!(2)).title = Some("Day 2: Red-Nosed Reports".into());
+
+        // an answer that was accepted before progress was tracked, and one that was rejected.
+        let mut submissions = Submissions::default();
+        submissions.record(day!(3), 1, "161", SubmissionOutcome::Correct);
+        submissions.record(day!(3), 2, "48", SubmissionOutcome::Incorrect(None));
+
+        (progress, submissions)
+    }
+
+    #[test]
+    fn errors_if_marker_not_present() {
+        let (progress, submissions) = get_mock_progress();
+        let mut s = "# readme".to_string();
+        assert!(update_content(&mut s, DEFAULT_YEAR.unwrap(), &progress, &submissions).is_err());
+    }
+
+    #[test]
+    fn format_stars() {
+        let (progress, submissions) = get_mock_progress();
+        let year = Year::new(2024).unwrap();
+        let marker = if year.is_default() {
+            MARKER.to_string()
+        } else {
+            MARKER.replace(" --->", " 2024 --->")
+        };
+
+        // a single marker is expanded into a table, which is replaced in place afterwards.
+        let mut s = format!("foo\n{marker}\nbaz");
+        update_content(&mut s, year, &progress, &submissions).unwrap();
+        update_content(&mut s, year, &progress, &submissions).unwrap();
+
+        let expected = [
+            "foo",
+            &marker,
+            "## 2024 Results",
+            "",
+            "| Day | Puzzle | Part 1 | Part 2 |",
+            "| :---: | :--- | :---: | :---: |",
+            "| 1 | [Historian Hysteria](https://adventofcode.com/2024/day/1) | ⭐ | ⭐ |",
+            "| 3 | [Day 3](https://adventofcode.com/2024/day/3) | ⭐ |   |",
+            "",
+            "**Total: 3 ⭐**",
+            &marker,
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+}
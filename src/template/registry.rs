@@ -0,0 +1,43 @@
+/// Compiled-in registry of solutions, used to run several days in a single process.
+/// The registry itself is generated by `build.rs` and lives in the main binary.
+use crate::template::PuzzleId;
+
+/// A parameter that a solution declares in `solution!`, e.g. `size: usize = 71`.
+#[derive(Clone, Copy)]
+pub struct Param {
+    pub name: &'static str,
+    /// Type of the parameter as written in `solution!`, e.g. `usize`.
+    pub ty: &'static str,
+    /// Whether a value parses as the type of the parameter.
+    pub parses: fn(&str) -> bool,
+}
+
+/// Entry point of a single day's solution, created by the `solution!` macro.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub puzzle: PuzzleId,
+    /// Runs every part of the solution against the given input.
+    pub run: fn(PuzzleId, &str),
+    /// Parameters the solution declares.
+    pub params: &'static [Param],
+}
+
+impl Solution {
+    // Not part of the public API
+    /// Assign the solution to the puzzle of its binary.
+    /// Inside the main binary, the `solution!` macro can not know which binary a module belongs to.
+    #[doc(hidden)]
+    #[must_use]
+    pub const fn __for_bin(self, bin_name: &str) -> Self {
+        Self {
+            puzzle: PuzzleId::__for_bin(bin_name, self.puzzle.day),
+            run: self.run,
+            params: self.params,
+        }
+    }
+}
+
+/// Find the solution for a given puzzle.
+pub fn find(solutions: &[Solution], puzzle: PuzzleId) -> Option<&Solution> {
+    solutions.iter().find(|s| s.puzzle == puzzle)
+}
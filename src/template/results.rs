@@ -0,0 +1,449 @@
+/// Machine-readable channel that solution binaries use to report results to `run_multi`.
+/// When the `AOC_RESULTS_FILE` environment variable is set, every part appends one JSON line to that file.
+/// Solutions that run in process report to an in-memory collector instead.
+use std::{
+    collections::HashMap,
+    env,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    str::FromStr,
+    sync::Mutex,
+    time::Duration,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{limits::format_bytes, memory::Memory, stats::Stats};
+
+pub const RESULTS_FILE_ENV: &str = "AOC_RESULTS_FILE";
+
+/// Part number under which the timing of the optional parse step is reported.
+pub const PARSE_PART: u8 = 0;
+
+static COLLECTOR: Mutex<Option<Vec<PartResult>>> = Mutex::new(None);
+
+/// Outcome of running a single part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PartStatus {
+    Solved,
+    Unsolved,
+    /// The part panicked or exceeded a limit.
+    Failed(Failure),
+}
+
+impl PartStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            PartStatus::Solved => "solved",
+            PartStatus::Unsolved => "unsolved",
+            PartStatus::Failed(_) => "failed",
+        }
+    }
+}
+
+/// Reason why a part did not finish.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Failure {
+    /// Message and source location of a panic in a solution.
+    Panic {
+        message: String,
+        location: Option<String>,
+    },
+    /// The part was killed after running longer than the timeout.
+    TimedOut(Duration),
+    /// The part was killed after trying to allocate more memory than the limit, in bytes.
+    OutOfMemory(u64),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Panic {
+                message,
+                location: Some(location),
+            } => write!(f, "panicked at {location}: {message}"),
+            Failure::Panic {
+                message,
+                location: None,
+            } => write!(f, "panicked: {message}"),
+            Failure::TimedOut(timeout) => write!(f, "timed out after {timeout:.1?}"),
+            Failure::OutOfMemory(limit) => {
+                write!(f, "out of memory (limit {})", format_bytes(*limit))
+            }
+        }
+    }
+}
+
+/// Result of a single part, as reported by a solution binary.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartResult {
+    pub part: u8,
+    pub answer: Option<String>,
+    /// Median duration if the part was benched, the duration of its single run otherwise.
+    pub nanos: u128,
+    pub samples: u128,
+    /// Statistics over all samples, only present if the part was benched.
+    pub stats: Option<Stats>,
+    /// Heap usage of the first run, if it was measured.
+    pub memory: Option<Memory>,
+    pub status: PartStatus,
+}
+
+impl PartResult {
+    /// Report this result to the active collector, or append it to the file named by `AOC_RESULTS_FILE`.
+    /// Does nothing if neither is present.
+    pub fn emit(&self) -> Result<(), io::Error> {
+        if let Some(collected) = COLLECTOR.lock().unwrap().as_mut() {
+            collected.push(self.clone());
+            return Ok(());
+        }
+
+        let Ok(path) = env::var(RESULTS_FILE_ENV) else {
+            return Ok(());
+        };
+
+        let line = JsonValue::from(self)
+            .stringify()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{line}")
+    }
+}
+
+/// Run `f` and collect every result that is reported while it runs.
+pub fn collect<R>(f: impl FnOnce() -> R) -> (R, Vec<PartResult>) {
+    *COLLECTOR.lock().unwrap() = Some(vec![]);
+    let value = f();
+    let results = COLLECTOR.lock().unwrap().take().unwrap_or_default();
+    (value, results)
+}
+
+/// Read all results reported to a results file. A missing file yields no results.
+pub fn read_results_file(path: &Path) -> Result<Vec<PartResult>, String> {
+    let s = match fs::read_to_string(path) {
+        Ok(s) => s,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e.to_string()),
+    };
+
+    parse_results(&s)
+}
+
+fn parse_results(s: &str) -> Result<Vec<PartResult>, String> {
+    s.lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| {
+            let json = JsonValue::from_str(l).or(Err("result line is not valid JSON."))?;
+            PartResult::try_from(&json)
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartResult> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &PartResult) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("nanos".into(), JsonValue::Number(value.nanos as f64));
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "stats".into(),
+            value
+                .stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert(
+            "memory".into(),
+            value
+                .memory
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
+        );
+
+        if let PartStatus::Failed(failure) = &value.status {
+            map.insert("failure".into(), JsonValue::from(failure));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartResult {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected result to be a JSON object.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected result.part to be a number.")? as u8;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected result.answer to be null or string.")?;
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected result.nanos to be a number.")? as u128;
+
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected result.samples to be a number.")? as u128;
+
+        let stats = match json.get("stats") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(Stats::try_from(v)?),
+        };
+
+        let memory = match json.get("memory") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(Memory::try_from(v)?),
+        };
+
+        let status = match json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected result.status to be a string.")?
+            .as_str()
+        {
+            "solved" => PartStatus::Solved,
+            "unsolved" => PartStatus::Unsolved,
+            "failed" => PartStatus::Failed(Failure::try_from(
+                json.get("failure")
+                    .ok_or("Expected failed result to have a failure.")?,
+            )?),
+            x => return Err(format!("Unknown part status `{x}`.")),
+        };
+
+        Ok(PartResult {
+            part,
+            answer: answer.cloned(),
+            nanos,
+            samples,
+            stats,
+            memory,
+            status,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Failure> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Failure) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        match value {
+            Failure::Panic { message, location } => {
+                map.insert("kind".into(), JsonValue::String("panic".into()));
+                map.insert("message".into(), JsonValue::String(message.clone()));
+                map.insert(
+                    "location".into(),
+                    match location {
+                        Some(x) => JsonValue::String(x.clone()),
+                        None => JsonValue::Null,
+                    },
+                );
+            }
+            Failure::TimedOut(timeout) => {
+                map.insert("kind".into(), JsonValue::String("timeout".into()));
+                map.insert("nanos".into(), JsonValue::Number(timeout.as_nanos() as f64));
+            }
+            Failure::OutOfMemory(limit) => {
+                map.insert("kind".into(), JsonValue::String("memory".into()));
+                map.insert("bytes".into(), JsonValue::Number(*limit as f64));
+            }
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Failure {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected failure to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected failure.{key} to be a number."))
+        };
+
+        // NOTE: failures without a kind are panics.
+        match json
+            .get("kind")
+            .and_then(|v| v.get::<String>())
+            .map(String::as_str)
+        {
+            None | Some("panic") => {
+                let message = json
+                    .get("message")
+                    .and_then(|v| v.get::<String>())
+                    .ok_or("Expected failure.message to be a string.")?;
+
+                let location = json
+                    .get("location")
+                    .map(|v| if v.is_null() { None } else { v.get::<String>() })
+                    .ok_or("Expected failure.location to be null or string.")?;
+
+                Ok(Failure::Panic {
+                    message: message.clone(),
+                    location: location.cloned(),
+                })
+            }
+            Some("timeout") => Ok(Failure::TimedOut(Duration::from_nanos(
+                number("nanos")? as u64
+            ))),
+            Some("memory") => Ok(Failure::OutOfMemory(number("bytes")? as u64)),
+            Some(x) => Err(format!("Unknown failure kind `{x}`.")),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{collect, parse_results, Failure, PartResult, PartStatus};
+    use crate::template::{
+        memory::Memory,
+        stats::{OutlierRule, Stats},
+    };
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn collects_results_in_process() {
+        let result = PartResult {
+            part: 1,
+            answer: Some("42".into()),
+            nanos: 10,
+            samples: 1,
+            stats: None,
+            memory: None,
+            status: PartStatus::Solved,
+        };
+        let (value, results) = collect(|| {
+            result.emit().unwrap();
+            "done"
+        });
+        assert_eq!(value, "done");
+        assert_eq!(results, vec![result]);
+    }
+
+    #[test]
+    fn roundtrips_results() {
+        let result = PartResult {
+            part: 2,
+            answer: Some("multi\nline \"answer\" (1ns @ 5 samples)".into()),
+            nanos: 74_130_074,
+            samples: 99_999,
+            stats: Stats::from_samples(
+                &[
+                    Duration::from_nanos(74_130_074),
+                    Duration::from_nanos(74_130_080),
+                ],
+                OutlierRule::default(),
+            ),
+            memory: Some(Memory {
+                peak_bytes: 1_048_576,
+                allocations: 12,
+            }),
+            status: PartStatus::Solved,
+        };
+        let line = JsonValue::from(&result).stringify().unwrap();
+        assert!(!line.contains('\n'));
+        assert_eq!(parse_results(&line).unwrap(), vec![result]);
+    }
+
+    #[test]
+    fn parses_unsolved_parts() {
+        let results = parse_results(
+            r#"{"part":1,"answer":null,"nanos":10,"samples":1,"status":"unsolved"}
+
+{"part":2,"answer":"42","nanos":20,"samples":1,"status":"solved"}"#,
+        )
+        .unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].answer, None);
+        assert_eq!(results[0].status, PartStatus::Unsolved);
+        assert_eq!(results[1].answer, Some("42".into()));
+    }
+
+    #[test]
+    fn roundtrips_failures() {
+        let result = PartResult {
+            part: 1,
+            answer: None,
+            nanos: 0,
+            samples: 0,
+            stats: None,
+            memory: None,
+            status: PartStatus::Failed(Failure::Panic {
+                message: "called `Result::unwrap()` on an `Err` value".into(),
+                location: Some("src/bin/01.rs:12:5".into()),
+            }),
+        };
+        let line = JsonValue::from(&result).stringify().unwrap();
+        assert_eq!(parse_results(&line).unwrap(), vec![result.clone()]);
+
+        for failure in [
+            Failure::TimedOut(Duration::from_millis(1500)),
+            Failure::OutOfMemory(512 * 1024 * 1024),
+        ] {
+            let result = PartResult {
+                status: PartStatus::Failed(failure),
+                ..result.clone()
+            };
+            let line = JsonValue::from(&result).stringify().unwrap();
+            assert_eq!(parse_results(&line).unwrap(), vec![result]);
+        }
+
+        assert!(parse_results(
+            r#"{"part":1,"answer":null,"nanos":0,"samples":0,"status":"failed"}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn errors_for_malformed_lines() {
+        assert!(parse_results("Part 1: 0 (74.13ns @ 100000 samples)").is_err());
+        assert!(parse_results(r#"{"part":1,"status":"solved"}"#).is_err());
+        assert!(parse_results(
+            r#"{"part":1,"answer":null,"nanos":10,"samples":1,"status":"exploded"}"#
+        )
+        .is_err());
+    }
+}
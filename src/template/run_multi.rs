@@ -1,38 +1,59 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::{self, Display, Formatter},
+    io,
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    Day, InputSource, Limits, PuzzleId, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
 use super::{
     all_days,
-    timings::{Timing, Timings},
+    registry::Solution,
+    results::{PartResult, PARSE_PART},
+    timings::{PartTiming, Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
-
-    let mut need_space = false;
+/// Determines how the solutions for each day are invoked.
+#[derive(Clone, Copy)]
+pub enum RunMode<'a> {
+    /// Spawn `cargo run --bin <day>` for every day, within the given limits.
+    /// With more than one job, days run concurrently and their output is printed once a day is done.
+    Isolated {
+        is_release: bool,
+        limits: Limits,
+        jobs: usize,
+        /// Crate that contains the bins, e.g. a worktree of another commit. Defaults to the current directory.
+        root: Option<&'a Path>,
+    },
+    /// Call the compiled-in solutions in the current process.
+    InProcess(&'a [Solution]),
+}
 
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+pub fn run_multi(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    mode: RunMode,
+    is_timed: bool,
+    input: InputSource,
+) -> Option<Timings> {
+    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+    run_days(year, days_to_run, mode, is_timed, input, |day, results| {
+        if !results.is_empty() {
+            let val = child_commands::parse_exec_time(results, day);
+            timings.push(val);
+        }
+    });
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
-
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
+    print_failures(&timings);
 
     if is_timed {
         let timings = Timings { data: timings };
@@ -46,212 +67,653 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     }
 }
 
-#[allow(dead_code)]
+/// Summarize parts that panicked, so failures do not get lost in the output of the other days.
+fn print_failures(timings: &[Timing]) {
+    let failures: Vec<String> = timings
+        .iter()
+        .flat_map(|t| {
+            t.failures.iter().map(move |(part, failure)| {
+                let step = match *part {
+                    PARSE_PART => "parse".into(),
+                    part => format!("part {part}"),
+                };
+                format!("Day {} {step}: {failure}", t.day)
+            })
+        })
+        .collect();
+
+    if !failures.is_empty() {
+        println!("\n{ANSI_BOLD}Failed:{ANSI_RESET}");
+        for failure in failures {
+            println!("{failure}");
+        }
+    }
+}
+
+/// Run the given days of a year in order and pass the results reported by each day to `on_day`.
+pub fn run_days(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    mode: RunMode,
+    is_timed: bool,
+    input: InputSource,
+    mut on_day: impl FnMut(Day, &[PartResult]),
+) {
+    // NOTE: use non-duplicate, sorted day values.
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+
+    if let RunMode::Isolated {
+        is_release,
+        limits,
+        jobs,
+        root,
+    } = mode
+    {
+        if jobs > 1 {
+            let run = |puzzle| {
+                child_commands::run_solution(
+                    puzzle, is_timed, is_release, limits, input, true, root,
+                )
+            };
+            run_days_parallel(year, &days, is_release, jobs, root, run, on_day);
+            return;
+        }
+
+        // the timeout of a day should not include building it.
+        if limits.timeout.is_some() {
+            if let Err(e) = child_commands::build_solutions(is_release, root) {
+                eprintln!("Failed to build solutions: {e:?}");
+            }
+        }
+    }
+
+    for (i, &day) in days.iter().enumerate() {
+        print_day_header(day, i > 0);
+
+        let puzzle = PuzzleId::new(year, day);
+        let run = match mode {
+            RunMode::Isolated {
+                is_release,
+                limits,
+                root,
+                ..
+            } => child_commands::run_solution(
+                puzzle, is_timed, is_release, limits, input, false, root,
+            )
+            .map(|(results, output)| {
+                print!("{output}");
+                results
+            }),
+            RunMode::InProcess(solutions) => {
+                Ok(in_process::run_solution(solutions, puzzle, is_timed, input))
+            }
+        };
+
+        finish_day(day, run, &mut on_day);
+    }
+}
+
+/// Run days in up to `jobs` child processes at once. The output of each day is buffered and printed in day order.
+fn run_days_parallel(
+    year: Year,
+    days: &[Day],
+    is_release: bool,
+    jobs: usize,
+    root: Option<&Path>,
+    run: impl Fn(PuzzleId) -> Result<(Vec<PartResult>, String), Error> + Sync,
+    mut on_day: impl FnMut(Day, &[PartResult]),
+) {
+    // build all solutions upfront, so that the jobs do not wait for each other on cargo's build lock.
+    if let Err(e) = child_commands::build_solutions(is_release, root) {
+        eprintln!("Failed to build solutions: {e:?}");
+    }
+
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let tx = tx.clone();
+            let next = &next;
+            let run = &run;
+
+            scope.spawn(move || {
+                while let Some(&day) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    if tx.send((day, run(PuzzleId::new(year, day)))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        drop(tx);
+
+        let mut done = BTreeMap::new();
+        let mut pending = days.iter().enumerate().peekable();
+
+        for (day, run) in rx {
+            done.insert(day, run);
+
+            while let Some(run) = pending.peek().and_then(|(_, day)| done.remove(*day)) {
+                let (i, &day) = pending.next().unwrap();
+
+                print_day_header(day, i > 0);
+                let run = run.map(|(results, output)| {
+                    print!("{output}");
+                    results
+                });
+                finish_day(day, run, &mut on_day);
+            }
+        }
+    });
+}
+
+fn print_day_header(day: Day, need_space: bool) {
+    if need_space {
+        println!();
+    }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
+/// Pass the results of a day to `on_day`. A day whose results could not be collected is reported as failed
+/// without results, so that the remaining days still run.
+fn finish_day(
+    day: Day,
+    run: Result<Vec<PartResult>, Error>,
+    on_day: &mut impl FnMut(Day, &[PartResult]),
+) {
+    let results = match run {
+        Ok(results) => results,
+        Err(e) => {
+            println!("✖ Failed to run: {e}");
+            return on_day(day, &[]);
+        }
+    };
+
+    if results.is_empty() {
+        println!("Not solved.");
+    }
+
+    on_day(day, &results);
+}
+
 #[derive(Debug)]
 pub enum Error {
-    BrokenPipe,
+    Results(String),
     IO(io::Error),
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Results(e) => write!(f, "failed to read results: {e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IO(e)
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// Solutions that are compiled into the main binary via the registry.
+/// This module runs them in the current process and collects the results they report.
+mod in_process {
+    use std::panic;
+
+    use crate::template::{
+        params,
+        registry::{self, Solution},
+        results::{self, PartResult},
+        runner, InputSource, PuzzleId,
+    };
+
+    /// Run the registered solution for a given puzzle.
+    pub fn run_solution(
+        solutions: &[Solution],
+        puzzle: PuzzleId,
+        is_timed: bool,
+        input: InputSource,
+    ) -> Vec<PartResult> {
+        let Some(solution) = registry::find(solutions, puzzle) else {
+            return vec![];
+        };
+
+        runner::set_timed(is_timed);
+
+        // a panicking solution should not abort the remaining days, the panic hook already reports it.
+        let (_, results) = results::collect(|| {
+            panic::catch_unwind(|| {
+                let source = input;
+                let input = input.read(puzzle);
+                params::run_input(puzzle, source, &[], || (solution.run)(puzzle, &input));
+            })
+        });
+
+        runner::set_timed(false);
+        results
+    }
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting the results they report.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::Error;
+    use crate::template::{
+        memory::Memory,
+        results::{
+            read_results_file, Failure, PartResult, PartStatus, PARSE_PART, RESULTS_FILE_ENV,
+        },
+        runner, Day, InputSource, Limits, PuzzleId,
+    };
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
+        env, fs,
+        io::{self, BufRead, BufReader, Read},
+        path::{Path, PathBuf},
+        process::{self, Child, Command, Stdio},
         thread,
+        time::{Duration, Instant},
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    /// Time a child gets on top of its timeout to report it and exit, before it is killed.
+    const KILL_GRACE: Duration = Duration::from_secs(2);
+
+    /// Build solutions with the heap measurements of the current process.
+    /// NOTE: another tree, e.g. of `--against`, may not have the feature, so its solutions are built without it.
+    fn feature_args(root: Option<&Path>) -> &'static [&'static str] {
+        if cfg!(feature = "heap-stats") && root.is_none() {
+            &["--features", "heap-stats"]
+        } else {
+            &[]
         }
+    }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    /// Build the bins of all solutions of the crate at `root`, or of the current directory.
+    pub fn build_solutions(is_release: bool, root: Option<&Path>) -> Result<(), Error> {
+        let mut args = vec!["build", "--quiet", "--bins"];
+        args.extend(feature_args(root));
 
         if is_release {
             args.push("--release");
         }
 
+        Command::new("cargo")
+            .args(&args)
+            .current_dir(root.unwrap_or(Path::new(".")))
+            .status()?;
+        Ok(())
+    }
+
+    /// Run the solution bin for a given puzzle and collect the results it reports.
+    /// The output of the bin is forwarded as-is, unless `is_captured` is set. Then, it is returned alongside the results.
+    /// A solution that runs out of memory can not report this itself, it is detected from its output instead.
+    pub fn run_solution(
+        puzzle: PuzzleId,
+        is_timed: bool,
+        is_release: bool,
+        limits: Limits,
+        input: InputSource,
+        is_captured: bool,
+        root: Option<&Path>,
+    ) -> Result<(Vec<PartResult>, String), Error> {
+        let features = feature_args(root);
+        let root = root.unwrap_or(Path::new("."));
+
+        // skip command invocation for days that have not been scaffolded yet.
+        if !root.join(puzzle.bin_path()).exists() {
+            return Ok((vec![], String::new()));
+        }
+
+        let bin_name = puzzle.bin_name();
+        let mut args: Vec<String> = ["run", "--quiet", "--bin", &bin_name]
+            .map(String::from)
+            .into();
+
+        args.extend(features.iter().map(|x| (*x).to_string()));
+
+        if is_release {
+            args.push("--release".into());
+        }
+
+        args.push("--".into());
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+            args.push("--time".into());
         }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        args.extend(input.to_args());
 
-        let mut cmd = Command::new("cargo")
+        // the child writes its results to a dedicated file, its output is forwarded as-is.
+        let results_path = get_results_path(puzzle);
+        let _ = fs::remove_file(&results_path);
+
+        let mut command = Command::new("cargo");
+        command
             .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
+            .current_dir(root)
+            .env(RESULTS_FILE_ENV, &results_path);
+        limits.apply_to(&mut command);
+
+        // NOTE: the child ends itself once its timeout is reached. The parent only kills it if that did not happen,
+        // e.g. because it hung before enforcing its limits.
+        let deadline = limits.timeout.map(|timeout| timeout + KILL_GRACE);
+        if deadline.is_some() {
+            own_process_group(&mut command);
+        }
+        let watch_allocations = limits.memory.is_some();
+
+        let mut child = command
+            .stdout(if is_captured {
+                Stdio::piped()
+            } else {
+                Stdio::inherit()
+            })
+            .stderr(if is_captured || watch_allocations {
+                Stdio::piped()
+            } else {
+                Stdio::inherit()
+            })
             .spawn()?;
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        let stdout = child.stdout.take().map(|mut stdout| {
+            thread::spawn(move || {
+                let mut s = String::new();
+                let _ = stdout.read_to_string(&mut s);
+                s
+            })
+        });
 
-        let mut output = vec![];
+        // stderr is scanned for allocation failures, and forwarded unless the output is captured.
+        let stderr = child.stderr.take().map(|stderr| {
+            thread::spawn(move || {
+                let mut captured = String::new();
+                let mut is_out_of_memory = false;
+                for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                    is_out_of_memory |= is_allocation_failure(&line);
+                    if is_captured {
+                        captured.push_str(&line);
+                        captured.push('\n');
+                    } else {
+                        eprintln!("{line}");
+                    }
+                }
+                (captured, is_out_of_memory)
+            })
+        });
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+        let is_killed = wait_with_deadline(&mut child, deadline)?;
+
+        let mut output = stdout
+            .map(|t| t.join().unwrap_or_default())
+            .unwrap_or_default();
+        let (stderr, is_out_of_memory) = stderr
+            .map(|t| t.join().unwrap_or_default())
+            .unwrap_or_default();
+        output.push_str(&stderr);
+
+        let results = read_results_file(&results_path);
+        let _ = fs::remove_file(&results_path);
+
+        let mut results = results.map_err(Error::Results)?;
+
+        let failure = match (limits.timeout, limits.memory) {
+            (Some(timeout), _) if is_killed => Some(Failure::TimedOut(timeout)),
+            (_, Some(memory)) if watch_allocations && is_out_of_memory => {
+                Some(Failure::OutOfMemory(memory))
+            }
+            _ => None,
+        };
+
+        if let Some(failure) = failure {
+            // the part that failed is the one after the last reported part.
+            let part = results.iter().map(|r| r.part + 1).max().unwrap_or(1);
+            output.push_str(&format!("Part {part}: ✖ {failure}\n"));
+            results.push(PartResult {
+                part,
+                answer: None,
+                nanos: 0,
+                samples: 0,
+                stats: None,
+                memory: None,
+                status: PartStatus::Failed(failure),
             });
-        });
+        }
+
+        Ok((results, output))
+    }
+
+    /// Wait for a child to exit, killing it once the deadline has passed. Returns whether it was killed.
+    fn wait_with_deadline(child: &mut Child, deadline: Option<Duration>) -> Result<bool, Error> {
+        let Some(deadline) = deadline else {
+            child.wait()?;
+            return Ok(false);
+        };
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        let start = Instant::now();
+
+        while child.try_wait()?.is_none() {
+            if start.elapsed() >= deadline {
+                kill(child)?;
+                child.wait()?;
+                return Ok(true);
+            }
+            thread::sleep(Duration::from_millis(10));
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        Ok(false)
+    }
 
-        Ok(output)
+    /// Start the child in a process group of its own. Killing the group ends the solution bin behind `cargo run`,
+    /// and every process the bin started, instead of leaving them orphaned.
+    /// NOTE: a group that is not in the foreground does not get the Ctrl+C of the terminal. The watchdog of the bin still ends it.
+    #[cfg(target_os = "linux")]
+    fn own_process_group(command: &mut Command) {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
+    #[cfg(not(target_os = "linux"))]
+    fn own_process_group(_command: &mut Command) {}
+
+    /// Kill a child and, on Linux, the other processes of its group.
+    #[cfg(target_os = "linux")]
+    fn kill(child: &mut Child) -> Result<(), io::Error> {
+        // SAFETY: `kill` has no memory safety requirements. The negated id addresses the group the child leads.
+        if unsafe { libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL) } == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn kill(child: &mut Child) -> Result<(), io::Error> {
+        child.kill()
+    }
+
+    /// Rust aborts with `memory allocation of <n> bytes failed` if the allocator can not serve a request.
+    fn is_allocation_failure(line: &str) -> bool {
+        line.starts_with("memory allocation of ") && line.ends_with(" bytes failed")
+    }
+
+    fn get_results_path(puzzle: PuzzleId) -> PathBuf {
+        env::temp_dir().join(format!(
+            "aoc-results-{}-{}.jsonl",
+            process::id(),
+            puzzle.bin_name()
+        ))
+    }
+
+    pub fn parse_exec_time(results: &[PartResult], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            part_1_stats: None,
+            part_2_stats: None,
+            parse: None,
+            parse_stats: None,
+            failures: vec![],
+            memory: None,
+            parts: vec![],
+            metadata: None,
         };
 
-        output
+        for r in results {
+            if let PartStatus::Failed(failure) = &r.status {
+                timings.failures.push((r.part, failure.clone()));
+            }
+        }
+
+        results
             .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
+            .filter(|r| r.status == PartStatus::Solved && r.samples > 1)
+            .for_each(|r| {
+                let timing_str = format_nanos(r.nanos);
+
+                if let Some(memory) = r.memory {
+                    let day = timings.memory.get_or_insert_with(Memory::default);
+                    day.peak_bytes = day.peak_bytes.max(memory.peak_bytes);
+                    day.allocations += memory.allocations;
                 }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                #[allow(clippy::cast_possible_truncation)]
+                timings.parts.push(super::PartTiming {
+                    part: r.part,
+                    nanos: r.nanos as u64,
+                    samples: Some(r.samples as u64),
+                });
+
+                if r.part == PARSE_PART {
+                    timings.parse = Some(timing_str);
+                    timings.parse_stats = r.stats;
+                } else if r.part == 1 {
+                    timings.part_1 = Some(timing_str);
+                    timings.part_1_stats = r.stats;
+                } else if r.part == 2 {
+                    timings.part_2 = Some(timing_str);
+                    timings.part_2_stats = r.stats;
                 }
 
-                timings.total_nanos += nanos;
+                #[allow(clippy::cast_precision_loss)]
+                {
+                    timings.total_nanos += r.nanos as f64;
+                }
             });
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    /// Format raw nanoseconds with the display the runner printed for them.
+    #[allow(clippy::cast_possible_truncation)]
+    fn format_nanos(nanos: u128) -> String {
+        runner::format_time(Duration::from_nanos(nanos as u64))
     }
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{is_allocation_failure, parse_exec_time};
+        use std::time::Duration;
+
+        use crate::{
+            day,
+            template::{
+                results::{Failure, PartResult, PartStatus},
+                runner,
+            },
+        };
 
-        use crate::day;
+        fn result(part: u8, answer: Option<&str>, nanos: u128, samples: u128) -> PartResult {
+            PartResult {
+                part,
+                answer: answer.map(Into::into),
+                nanos,
+                samples,
+                stats: None,
+                memory: None,
+                status: if answer.is_some() {
+                    PartStatus::Solved
+                } else {
+                    PartStatus::Unsolved
+                },
+            }
+        }
 
         #[test]
         fn parses_execution_times() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    result(1, Some("0"), 74, 100_000),
+                    result(2, Some("10"), 74_130_000, 99_999),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.total_nanos, 74_130_074_f64);
+            // the display matches what the runner printed for the part.
+            assert_eq!(
+                res.part_1.as_deref(),
+                Some(runner::format_time(Duration::from_nanos(74)).as_str())
+            );
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+            assert_eq!(res.part_2.unwrap(), "74.1ms");
         }
 
         #[test]
         fn parses_with_patterns_in_input() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    result(1, Some("@ @ @ ( ) ms (2s @ 5 samples)"), 2_000_000_000, 5),
+                    result(2, Some("10s\n(1ms @ 2 samples)"), 100_000_000, 10),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.total_nanos, 2_100_000_000_f64);
+            assert_eq!(res.part_1.unwrap(), "2.0s");
+            assert_eq!(res.part_2.unwrap(), "100.0ms");
         }
 
         #[test]
         fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
+            let res = parse_exec_time(&[result(1, None, 10, 1), result(2, None, 10, 1)], day!(1));
+            assert_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn keeps_failures() {
+            let failure = Failure::Panic {
+                message: "boom".into(),
+                location: Some("src/bin/01.rs:3:5".into()),
+            };
+            let mut failed = result(1, None, 0, 0);
+            failed.status = PartStatus::Failed(failure.clone());
+
+            let res = parse_exec_time(&[failed, result(2, Some("1"), 10, 10)], day!(1));
+            assert_eq!(res.failures, vec![(1, failure)]);
+            assert_eq!(res.part_1.is_none(), true);
+            assert_eq!(res.part_2.unwrap(), "10.0ns");
+        }
+
+        #[test]
+        fn detects_allocation_failures() {
+            assert!(is_allocation_failure(
+                "memory allocation of 34359738368 bytes failed"
+            ));
+            assert!(!is_allocation_failure("Part 1: 42 (1.0ms)"));
+        }
+
+        #[test]
+        fn ignores_untimed_parts() {
+            let res = parse_exec_time(&[result(1, Some("1"), 10, 1)], day!(1));
+            assert_eq!(res.total_nanos, 0_f64);
+            assert_eq!(res.part_1.is_none(), true);
+        }
     }
 }
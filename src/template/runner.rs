@@ -1,35 +1,232 @@
 /// Encapsulates code that interacts with solution functions.
+use std::cell::{Cell, RefCell};
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe, PanicHookInfo};
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::Once;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::template::answers::Answers;
+use crate::template::aoc_cli::SubmissionOutcome;
+use crate::template::client::{self, ClientError};
+use crate::template::memory::{self, Memory};
+use crate::template::progress::{self, Progress};
+use crate::template::results::{Failure, PartResult, PartStatus, PARSE_PART};
+use crate::template::stats::{OutlierRule, Stats};
+use crate::template::submissions::Submissions;
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{InputSource, PuzzleId, ANSI_ITALIC, ANSI_RESET};
+
+static TIMED: AtomicBool = AtomicBool::new(false);
+static PANIC_HOOK: Once = Once::new();
+/// Part that is currently running, so that a watchdog can report which part it interrupted.
+static CURRENT_PART: AtomicU8 = AtomicU8::new(1);
+
+thread_local! {
+    static CAPTURING: Cell<bool> = const { Cell::new(false) };
+    static LAST_PANIC: RefCell<Option<Failure>> = const { RefCell::new(None) };
+    static PARTS: RefCell<Option<Vec<u8>>> = const { RefCell::new(None) };
+}
+
+/// Bench all parts that are run from now on, even if `--time` was not passed to the process.
+pub fn set_timed(is_timed: bool) {
+    TIMED.store(is_timed, Ordering::Relaxed);
+}
+
+fn is_timed() -> bool {
+    TIMED.load(Ordering::Relaxed) || env::args().any(|x| x == "--time")
+}
+
+/// Only run the given parts while `f` runs on the current thread, e.g. the parts an example has answers for.
+/// The previous selection is restored even if `f` panics.
+pub fn with_parts<R>(parts: &[u8], f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<Vec<u8>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            PARTS.set(self.0.take());
+        }
+    }
+
+    let _restore = Restore(PARTS.replace(Some(parts.to_vec())));
+    f()
+}
+
+fn is_selected(part: u8) -> bool {
+    PARTS.with_borrow(|parts| parts.as_ref().is_none_or(|parts| parts.contains(&part)))
+}
+
+pub fn run_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+) {
+    if !is_selected(part) {
+        return;
+    }
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
+    CURRENT_PART.store(part, Ordering::Relaxed);
+
+    let (result, duration, stats, memory) = match catch_failure(|| {
+        run_timed(func, input, |result| print_result(result, &part_str, ""))
+    }) {
+        Ok(outcome) => outcome,
+        Err(failure) => {
+            report_failure(failure, &part_str, part);
+            return;
+        }
+    };
+
+    print_result(
+        &result,
+        &part_str,
+        &format_duration(&duration, stats.as_ref()),
+    );
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let part_result = PartResult {
+        part,
+        answer: result.as_ref().map(ToString::to_string),
+        nanos: duration.as_nanos(),
+        samples: stats.map_or(1, |s| s.samples + s.outliers),
+        stats,
+        memory,
+        status: if result.is_some() {
+            PartStatus::Solved
+        } else {
+            PartStatus::Unsolved
+        },
+    };
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    if let Err(e) = part_result.emit() {
+        eprintln!("Failed to report result for {part_str}: {e}");
+    }
 
     if let Some(result) = result {
-        submit_result(result, day, part);
+        submit_result(result, puzzle, part);
+    }
+}
+
+/// Parse the input for all parts of a solution. The parse step is timed like a part and reported as part `0`.
+/// A panic while parsing is reported and then continues to unwind, as no part can run without the parsed input.
+pub fn run_parse<P>(func: impl Fn(&str) -> P, input: &str, puzzle: PuzzleId) -> P {
+    CURRENT_PART.store(PARSE_PART, Ordering::Relaxed);
+
+    let (parsed, duration, stats, memory) =
+        match catch_failure(|| run_timed(func, input, |_| print!("Parse: ✔"))) {
+            Ok(outcome) => outcome,
+            Err(failure) => {
+                report_failure(failure, "Parse", PARSE_PART);
+                panic::resume_unwind(Box::new("parse failed"));
+            }
+        };
+
+    print!("\r");
+    println!("Parse: ✔{}", format_duration(&duration, stats.as_ref()));
+
+    let part_result = PartResult {
+        part: PARSE_PART,
+        answer: None,
+        nanos: duration.as_nanos(),
+        samples: stats.map_or(1, |s| s.samples + s.outliers),
+        stats,
+        memory,
+        status: PartStatus::Solved,
+    };
+
+    if let Err(e) = part_result.emit() {
+        eprintln!("Failed to report parse result for {puzzle}: {e}");
+    }
+
+    parsed
+}
+
+/// Run `f` and turn a panic into a failure.
+/// While `f` runs, the panic hook records the panic message and location instead of printing them.
+fn catch_failure<T>(f: impl FnOnce() -> T) -> Result<T, Failure> {
+    PANIC_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CAPTURING.get() {
+                LAST_PANIC.set(Some(failure_from_panic(info)));
+            } else {
+                default_hook(info);
+            }
+        }));
+    });
+
+    CAPTURING.set(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CAPTURING.set(false);
+
+    result.map_err(|_| {
+        LAST_PANIC.take().unwrap_or_else(|| Failure::Panic {
+            message: "unknown panic".into(),
+            location: None,
+        })
+    })
+}
+
+fn failure_from_panic(info: &PanicHookInfo) -> Failure {
+    let payload = info.payload();
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|s| (*s).to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Box<dyn Any>".into());
+
+    Failure::Panic {
+        message,
+        location: info.location().map(ToString::to_string),
+    }
+}
+
+/// Report that the running part was interrupted, e.g. because it exceeded a limit.
+pub(crate) fn report_interrupted(failure: Failure) {
+    let part = CURRENT_PART.load(Ordering::Relaxed);
+    let part_str = if part == PARSE_PART {
+        "Parse".into()
+    } else {
+        format!("Part {part}")
+    };
+    report_failure(failure, &part_str, part);
+}
+
+fn report_failure(failure: Failure, part_str: &str, part: u8) {
+    print!("\r");
+    println!("{part_str}: ✖ {failure}");
+
+    let part_result = PartResult {
+        part,
+        answer: None,
+        nanos: 0,
+        samples: 0,
+        stats: None,
+        memory: None,
+        status: PartStatus::Failed(failure),
+    };
+
+    if let Err(e) = part_result.emit() {
+        eprintln!("Failed to report result for {part_str}: {e}");
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///     The returned duration is the median of all samples then.
+///
+/// When benched with the `heap-stats` feature, the heap usage is measured in an extra, untimed execution,
+/// as counting allocations slows them down.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, Option<Stats>, Option<Memory>) {
     let timer = Instant::now();
     let result = {
         let input = input.clone();
@@ -43,16 +240,24 @@ fn run_timed<I: Clone, T>(
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let (stats, memory) = if is_timed() {
+        let memory = if memory::ENABLED {
+            memory::measure(|| black_box(func(input.clone()))).1
+        } else {
+            None
+        };
+        (bench(func, input, &base_time), memory)
     } else {
-        (base_time, 1)
+        (None, None)
     };
 
-    (result, run.0, run.1)
+    let duration = stats.map_or(base_time, |s| s.median);
+    (result, duration, stats, memory)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+/// Bench a function after warming up caches and branch predictors with a tenth of the iterations.
+/// Outliers are rejected according to the rule in `AOC_OUTLIERS`.
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> Option<Stats> {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
@@ -60,6 +265,11 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
 
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let warmup_iterations = bench_iterations / 10;
+
+    for _ in 0..warmup_iterations {
+        black_box(func(black_box(input.clone())));
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -71,26 +281,35 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    Stats::from_samples(&timers, OutlierRule::from_env())
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+/// Display of a duration as printed next to an answer. Timings store the same display, so that the readme
+/// shows exactly what `cargo time` printed.
+pub fn format_time(duration: Duration) -> String {
+    format!("{duration:.1?}")
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+fn format_duration(duration: &Duration, stats: Option<&Stats>) -> String {
+    let duration = format_time(*duration);
+
+    match stats {
+        None => format!(" ({duration})"),
+        Some(stats) => {
+            let outliers = match stats.outliers {
+                0 => String::new(),
+                1 => ", 1 outlier".into(),
+                n => format!(", {n} outliers"),
+            };
+            format!(
+                " ({duration} @ {} samples{outliers}; min {:.1?}, max {:.1?}, σ {:.1?}, p95 {:.1?})",
+                stats.samples + stats.outliers,
+                stats.min,
+                stats.max,
+                stats.stddev,
+                stats.p95
+            )
+        }
     }
 }
 
@@ -131,18 +350,24 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. aoc-cli is installed or a session cookie is configured for the built-in client.
+///  3. the answer was not rejected before, and is not out of the bounds of earlier submissions.
 fn submit_result<T: Display>(
     result: T,
-    day: Day,
+    puzzle: PuzzleId,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<SubmissionOutcome, ClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
         return None;
     }
 
+    if InputSource::from_args() != InputSource::Puzzle {
+        eprintln!("Answers to examples are not submitted.");
+        return None;
+    }
+
     if args.len() < 3 {
         eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
         process::exit(1);
@@ -159,11 +384,88 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    let client = match client::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    let result = result.to_string();
+    // NOTE: without the history, known wrong answers can not be refused and the history would be overwritten.
+    let mut submissions = match Submissions::read_from_file(puzzle.year) {
+        Ok(submissions) => submissions,
+        Err(e) => {
+            eprintln!("Refusing to submit {result}: {e}");
+            return None;
+        }
+    };
+
+    if let Err(refusal) = submissions.check(puzzle.day, part, &result) {
+        eprintln!("Refusing to submit {result}: {refusal}");
+        return None;
+    }
+
+    println!("Submitting result...");
+
+    let outcome = client.submit(puzzle, part, &result);
+
+    match outcome {
+        // a rate-limited answer was not judged, so there is nothing to record.
+        Ok(SubmissionOutcome::RateLimited(wait)) => {
+            let wait = wait.map_or(String::new(), |w| format!(" in {w:?}"));
+            eprintln!("Submission was rate-limited, try again{wait}.");
+        }
+        Ok(outcome) => {
+            submissions.record(puzzle.day, part, &result, outcome);
+            if let Err(e) = submissions.store_file(puzzle.year) {
+                eprintln!("Failed to record submission: {e}");
+            }
+
+            if outcome == SubmissionOutcome::Correct {
+                record_answer(puzzle, part, &result);
+                Progress::record(puzzle, |day| match part {
+                    1 => day.part_1 = Some(progress::now()),
+                    _ => day.part_2 = Some(progress::now()),
+                });
+            }
+        }
+        Err(ref e) => eprintln!("{e}"),
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(outcome)
+}
+
+/// Store an accepted answer, so `cargo verify` can check it later.
+fn record_answer(puzzle: PuzzleId, part: u8, result: &str) {
+    let mut answers = match Answers::read_from_file(puzzle.year) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("Failed to record accepted answer: {e}");
+            return;
+        }
+    };
+    answers.set(puzzle.day, part, result);
+
+    match answers.store_file(puzzle.year) {
+        Ok(()) => println!("Recorded accepted answer for {puzzle}, part {part}."),
+        Err(e) => eprintln!("Failed to record accepted answer: {e}"),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{is_selected, with_parts};
+    use std::panic;
+
+    #[test]
+    fn restores_selected_parts_after_panics() {
+        let result = panic::catch_unwind(|| with_parts(&[2], || panic!("part 2 failed")));
+
+        assert!(result.is_err());
+        assert!(is_selected(1));
+    }
 }
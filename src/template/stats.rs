@@ -0,0 +1,278 @@
+/// Summary statistics over the samples collected while benchmarking a part.
+use std::{collections::HashMap, env, fmt::Display, str::FromStr, time::Duration};
+use tinyjson::JsonValue;
+
+/// Environment variable that selects the [`OutlierRule`], e.g. `iqr:3` or `none`.
+pub const OUTLIERS_ENV: &str = "AOC_OUTLIERS";
+
+/// Rule for dropping samples that are disturbed by the OS before computing statistics.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OutlierRule {
+    /// Keep every sample.
+    Keep,
+    /// Drop samples outside of `k` times the interquartile range beyond the quartiles (Tukey's fences).
+    Iqr(f64),
+    /// Drop samples further than `k` median absolute deviations from the median.
+    Mad(f64),
+}
+
+impl Default for OutlierRule {
+    fn default() -> Self {
+        OutlierRule::Iqr(1.5)
+    }
+}
+
+impl OutlierRule {
+    /// Read the rule from `AOC_OUTLIERS`, falling back to the default rule.
+    pub fn from_env() -> Self {
+        match env::var(OUTLIERS_ENV) {
+            Ok(s) => s.parse().unwrap_or_else(|e| {
+                eprintln!("Ignoring {OUTLIERS_ENV}: {e}");
+                OutlierRule::default()
+            }),
+            Err(_) => OutlierRule::default(),
+        }
+    }
+
+    /// Use this rule for all parts benched from now on, including child processes.
+    pub fn apply(self) {
+        env::set_var(OUTLIERS_ENV, self.to_string());
+    }
+
+    fn is_outlier(self, nanos: f64, median: f64, q1: f64, q3: f64, mad: f64) -> bool {
+        match self {
+            OutlierRule::Keep => false,
+            OutlierRule::Iqr(k) => {
+                let iqr = q3 - q1;
+                nanos < q1 - k * iqr || nanos > q3 + k * iqr
+            }
+            OutlierRule::Mad(k) => (nanos - median).abs() > k * mad,
+        }
+    }
+}
+
+impl FromStr for OutlierRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, k) = match s.split_once(':') {
+            Some((name, k)) => (
+                name,
+                Some(
+                    k.parse::<f64>()
+                        .ok()
+                        .filter(|k| *k > 0.0)
+                        .ok_or(format!("expected a positive factor, got `{k}`."))?,
+                ),
+            ),
+            None => (s, None),
+        };
+
+        match name {
+            "none" if k.is_none() => Ok(OutlierRule::Keep),
+            "iqr" => Ok(OutlierRule::Iqr(k.unwrap_or(1.5))),
+            "mad" => Ok(OutlierRule::Mad(k.unwrap_or(3.0))),
+            x => Err(format!(
+                "unknown outlier rule `{x}`, expecting `none`, `iqr[:k]` or `mad[:k]`."
+            )),
+        }
+    }
+}
+
+impl Display for OutlierRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutlierRule::Keep => write!(f, "none"),
+            OutlierRule::Iqr(k) => write!(f, "iqr:{k}"),
+            OutlierRule::Mad(k) => write!(f, "mad:{k}"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Statistics over the samples of a benchmark, after outliers were rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub mean: Duration,
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub stddev: Duration,
+    pub p95: Duration,
+    /// Number of samples that were kept.
+    pub samples: u128,
+    /// Number of samples that were rejected as outliers.
+    pub outliers: u128,
+}
+
+impl Stats {
+    /// Compute statistics over `samples`. Returns `None` if there are no samples.
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn from_samples(samples: &[Duration], rule: OutlierRule) -> Option<Self> {
+        let mut sorted: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        sorted.sort_by(f64::total_cmp);
+
+        let median = percentile(&sorted, 0.5)?;
+        let (q1, q3) = (percentile(&sorted, 0.25)?, percentile(&sorted, 0.75)?);
+
+        let mut deviations: Vec<f64> = sorted.iter().map(|x| (x - median).abs()).collect();
+        deviations.sort_by(f64::total_cmp);
+        let mad = percentile(&deviations, 0.5)?;
+
+        let kept: Vec<f64> = sorted
+            .iter()
+            .copied()
+            .filter(|x| !rule.is_outlier(*x, median, q1, q3, mad))
+            .collect();
+
+        let n = kept.len() as f64;
+        let mean = kept.iter().sum::<f64>() / n;
+        let variance = kept.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+        let to_duration = |nanos: f64| Duration::from_nanos(nanos.round() as u64);
+
+        Some(Stats {
+            mean: to_duration(mean),
+            median: to_duration(percentile(&kept, 0.5)?),
+            min: to_duration(*kept.first()?),
+            max: to_duration(*kept.last()?),
+            stddev: to_duration(variance.sqrt()),
+            p95: to_duration(percentile(&kept, 0.95)?),
+            samples: kept.len() as u128,
+            outliers: (sorted.len() - kept.len()) as u128,
+        })
+    }
+}
+
+/// Linearly interpolated percentile of sorted values.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn percentile(sorted: &[f64], p: f64) -> Option<f64> {
+    let last = sorted.len().checked_sub(1)?;
+    let rank = p * last as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    Some(sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - rank.floor()))
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Stats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Stats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        for (key, duration) in [
+            ("mean", value.mean),
+            ("median", value.median),
+            ("min", value.min),
+            ("max", value.max),
+            ("stddev", value.stddev),
+            ("p95", value.p95),
+        ] {
+            map.insert(key.into(), JsonValue::Number(duration.as_nanos() as f64));
+        }
+
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Stats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+        let duration = |key: &str| number(key).map(|n| Duration::from_nanos(n as u64));
+
+        Ok(Stats {
+            mean: duration("mean")?,
+            median: duration("median")?,
+            min: duration("min")?,
+            max: duration("max")?,
+            stddev: duration("stddev")?,
+            p95: duration("p95")?,
+            samples: number("samples")? as u128,
+            outliers: number("outliers")? as u128,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{OutlierRule, Stats};
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    fn samples(nanos: &[u64]) -> Vec<Duration> {
+        nanos.iter().map(|n| Duration::from_nanos(*n)).collect()
+    }
+
+    #[test]
+    fn computes_statistics() {
+        let stats = Stats::from_samples(&samples(&[40, 10, 30, 20]), OutlierRule::Keep).unwrap();
+        assert_eq!(stats.mean, Duration::from_nanos(25));
+        assert_eq!(stats.median, Duration::from_nanos(25));
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.max, Duration::from_nanos(40));
+        assert_eq!(stats.stddev, Duration::from_nanos(11));
+        assert_eq!(stats.p95, Duration::from_nanos(39));
+        assert_eq!(stats.samples, 4);
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(Stats::from_samples(&[], OutlierRule::Keep), None);
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let nanos = samples(&[10, 11, 10, 12, 11, 10, 500]);
+
+        let iqr = Stats::from_samples(&nanos, OutlierRule::default()).unwrap();
+        assert_eq!(iqr.outliers, 1);
+        assert_eq!(iqr.max, Duration::from_nanos(12));
+
+        let mad = Stats::from_samples(&nanos, OutlierRule::Mad(3.0)).unwrap();
+        assert_eq!(mad.outliers, 1);
+
+        let kept = Stats::from_samples(&nanos, OutlierRule::Keep).unwrap();
+        assert_eq!(kept.outliers, 0);
+        assert_eq!(kept.max, Duration::from_nanos(500));
+    }
+
+    #[test]
+    fn parses_outlier_rules() {
+        assert_eq!("none".parse(), Ok(OutlierRule::Keep));
+        assert_eq!("iqr".parse(), Ok(OutlierRule::Iqr(1.5)));
+        assert_eq!("iqr:3".parse(), Ok(OutlierRule::Iqr(3.0)));
+        assert_eq!("mad:2.5".parse(), Ok(OutlierRule::Mad(2.5)));
+        assert!("iqr:-1".parse::<OutlierRule>().is_err());
+        assert!("none:2".parse::<OutlierRule>().is_err());
+        assert!("zscore".parse::<OutlierRule>().is_err());
+        assert_eq!(OutlierRule::Mad(2.5).to_string(), "mad:2.5");
+    }
+
+    #[test]
+    fn roundtrips_stats() {
+        let stats = Stats::from_samples(&samples(&[1, 2, 3, 1000]), OutlierRule::Keep).unwrap();
+        let json = JsonValue::from(&stats);
+        assert_eq!(Stats::try_from(&json).unwrap(), stats);
+    }
+}
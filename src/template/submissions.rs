@@ -0,0 +1,359 @@
+/// Local history of submitted answers, used to avoid submitting answers that are known to be wrong.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::{Error, ErrorKind},
+    path::PathBuf,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{aoc_cli::SubmissionOutcome, Day, Year};
+
+static SUBMISSIONS_FILE_NAME: &str = "submissions.json";
+
+fn verdict_to_str(verdict: SubmissionOutcome) -> &'static str {
+    match verdict {
+        SubmissionOutcome::Correct => "correct",
+        SubmissionOutcome::Incorrect(_) => "incorrect",
+        SubmissionOutcome::TooHigh(_) => "too_high",
+        SubmissionOutcome::TooLow(_) => "too_low",
+        SubmissionOutcome::RateLimited(_) => "rate_limited",
+        SubmissionOutcome::WrongLevel => "wrong_level",
+        SubmissionOutcome::Unknown => "unknown",
+    }
+}
+
+fn verdict_from_str(s: &str) -> Result<SubmissionOutcome, String> {
+    match s {
+        "correct" => Ok(SubmissionOutcome::Correct),
+        "incorrect" => Ok(SubmissionOutcome::Incorrect(None)),
+        "too_high" => Ok(SubmissionOutcome::TooHigh(None)),
+        "too_low" => Ok(SubmissionOutcome::TooLow(None)),
+        "rate_limited" => Ok(SubmissionOutcome::RateLimited(None)),
+        "wrong_level" => Ok(SubmissionOutcome::WrongLevel),
+        "unknown" => Ok(SubmissionOutcome::Unknown),
+        x => Err(format!("Unknown submission verdict `{x}`.")),
+    }
+}
+
+/// Represents a single submitted answer.
+#[derive(Clone, Debug)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub verdict: SubmissionOutcome,
+}
+
+/// Reasons for refusing to submit an answer.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadyRejected(SubmissionOutcome),
+    TooHigh { bound: String },
+    TooLow { bound: String },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadyRejected(verdict) => {
+                write!(f, "this answer was already submitted and is {verdict}.")
+            }
+            Refusal::TooHigh { bound } => {
+                write!(
+                    f,
+                    "answers of {bound} or more were already rejected as too high."
+                )
+            }
+            Refusal::TooLow { bound } => {
+                write!(
+                    f,
+                    "answers of {bound} or less were already rejected as too low."
+                )
+            }
+        }
+    }
+}
+
+/// Represents the history of submitted answers.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    pub fn file_path(year: Year) -> PathBuf {
+        year.data_dir().join(SUBMISSIONS_FILE_NAME)
+    }
+
+    /// Dehydrate submissions to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(Submissions::file_path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate submissions from a JSON file. If not present, returns an empty history.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        match fs::read_to_string(Submissions::file_path(year)) {
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Submissions::default()),
+            s => s.map_err(|x| x.to_string()).and_then(Submissions::try_from),
+        }
+        .map_err(|e| {
+            format!(
+                "Failed to read \"{}\": {e}",
+                Submissions::file_path(year).display()
+            )
+        })
+    }
+
+    /// Append a submission that was made just now.
+    pub fn record(&mut self, day: Day, part: u8, answer: &str, verdict: SubmissionOutcome) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        self.data.push(Submission {
+            day,
+            part,
+            answer: answer.into(),
+            timestamp,
+            verdict,
+        });
+    }
+
+    /// Whether an answer to a part was accepted.
+    pub fn is_accepted(&self, day: Day, part: u8) -> bool {
+        self.data
+            .iter()
+            .any(|s| s.day == day && s.part == part && s.verdict == SubmissionOutcome::Correct)
+    }
+
+    /// Check an answer against earlier submissions for the same part.
+    /// Numeric answers are also checked against the recorded too high / too low bounds.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Result<(), Refusal> {
+        let submissions = self
+            .data
+            .iter()
+            .filter(|s| s.day == day && s.part == part && s.verdict.is_rejected());
+
+        let mut too_high: Option<(i128, &str)> = None;
+        let mut too_low: Option<(i128, &str)> = None;
+
+        for submission in submissions {
+            if submission.answer == answer {
+                return Err(Refusal::AlreadyRejected(submission.verdict));
+            }
+
+            let Ok(value) = submission.answer.trim().parse::<i128>() else {
+                continue;
+            };
+
+            match submission.verdict {
+                SubmissionOutcome::TooHigh(_)
+                    if too_high.is_none_or(|(bound, _)| value < bound) =>
+                {
+                    too_high = Some((value, &submission.answer));
+                }
+                SubmissionOutcome::TooLow(_) if too_low.is_none_or(|(bound, _)| value > bound) => {
+                    too_low = Some((value, &submission.answer));
+                }
+                _ => {}
+            }
+        }
+
+        let Ok(value) = answer.trim().parse::<i128>() else {
+            return Ok(());
+        };
+
+        match (too_high, too_low) {
+            (Some((bound, s)), _) if value >= bound => Err(Refusal::TooHigh { bound: s.into() }),
+            (_, Some((bound, s))) if value <= bound => Err(Refusal::TooLow { bound: s.into() }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(verdict_to_str(value.verdict).into()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected submission.part to be a number.")? as u8;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected submission.timestamp to be a number.")? as u64;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.verdict to be a string.")?;
+
+        Ok(Submission {
+            day,
+            part,
+            answer: answer.clone(),
+            timestamp,
+            verdict: verdict_from_str(verdict)?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::day;
+
+    use super::{Refusal, Submissions};
+    use crate::template::aoc_cli::SubmissionOutcome;
+
+    fn get_mock_submissions() -> Submissions {
+        let mut submissions = Submissions::default();
+        submissions.record(day!(1), 1, "100", SubmissionOutcome::TooHigh(None));
+        submissions.record(day!(1), 1, "150", SubmissionOutcome::TooHigh(None));
+        submissions.record(day!(1), 1, "20", SubmissionOutcome::TooLow(None));
+        submissions.record(day!(1), 1, "50", SubmissionOutcome::Incorrect(None));
+        submissions.record(day!(1), 1, "60", SubmissionOutcome::Unknown);
+        submissions.record(day!(1), 2, "abc", SubmissionOutcome::Incorrect(None));
+        submissions
+    }
+
+    #[test]
+    fn refuses_rejected_answers() {
+        let submissions = get_mock_submissions();
+        assert_eq!(
+            submissions.check(day!(1), 1, "50"),
+            Err(Refusal::AlreadyRejected(SubmissionOutcome::Incorrect(None)))
+        );
+        assert_eq!(
+            submissions.check(day!(1), 2, "abc"),
+            Err(Refusal::AlreadyRejected(SubmissionOutcome::Incorrect(None)))
+        );
+    }
+
+    #[test]
+    fn refuses_answers_out_of_bounds() {
+        let submissions = get_mock_submissions();
+        assert_eq!(
+            submissions.check(day!(1), 1, "120"),
+            Err(Refusal::TooHigh {
+                bound: "100".into()
+            })
+        );
+        assert_eq!(
+            submissions.check(day!(1), 1, "-3"),
+            Err(Refusal::TooLow { bound: "20".into() })
+        );
+    }
+
+    #[test]
+    fn accepts_other_answers() {
+        let submissions = get_mock_submissions();
+        assert_eq!(submissions.check(day!(1), 1, "60"), Ok(()));
+        assert_eq!(submissions.check(day!(1), 1, "99"), Ok(()));
+        assert_eq!(submissions.check(day!(1), 2, "120"), Ok(()));
+        assert_eq!(submissions.check(day!(2), 1, "50"), Ok(()));
+    }
+
+    #[test]
+    fn roundtrips_submissions() {
+        let json = tinyjson::JsonValue::from(get_mock_submissions())
+            .stringify()
+            .unwrap();
+        let submissions = Submissions::try_from(json).unwrap();
+        assert_eq!(submissions.data.len(), 6);
+        assert_eq!(
+            submissions.data[0].verdict,
+            SubmissionOutcome::TooHigh(None)
+        );
+        assert_eq!(submissions.data[5].answer, "abc");
+    }
+}
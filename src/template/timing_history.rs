@@ -0,0 +1,426 @@
+/// History of stored benchmarks, to follow how the timings of each part develop over commits.
+/// Every `cargo time --store` appends the days it benched, keyed by commit and date.
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    path::PathBuf,
+    str::FromStr,
+    time::Duration,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    bench_metadata::BenchMetadata,
+    timings::{PartTiming, Timings},
+    Day, Year, ANSI_BOLD, ANSI_RESET,
+};
+
+static HISTORY_FILE_NAME: &str = "timings_history.json";
+
+static SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Timings of a day at one commit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HistoryEntry {
+    pub day: Day,
+    pub metadata: BenchMetadata,
+    /// Raw durations of the parse step and parts, with the part number `0` standing for the parse step.
+    pub parts: Vec<PartTiming>,
+}
+
+impl HistoryEntry {
+    fn nanos(&self, part: u8) -> Option<u64> {
+        self.parts.iter().find(|p| p.part == part).map(|p| p.nanos)
+    }
+
+    /// Runs of the same day, commit and date replace each other, so that re-running a bench keeps one entry.
+    fn is_same_run(&self, other: &Self) -> bool {
+        self.day == other.day
+            && self.metadata.commit == other.metadata.commit
+            && self.metadata.date() == other.metadata.date()
+    }
+}
+
+/// Represents the benchmark history of a year.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct TimingHistory {
+    pub data: Vec<HistoryEntry>,
+}
+
+impl TimingHistory {
+    /// Path of the history file of a year.
+    pub fn file_path(year: Year) -> PathBuf {
+        year.data_dir().join(HISTORY_FILE_NAME)
+    }
+
+    /// Dehydrate the history to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(TimingHistory::file_path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the history from a JSON file. If not present, returns an empty history.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        match fs::read_to_string(TimingHistory::file_path(year)) {
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(TimingHistory::default()),
+            s => s
+                .map_err(|x| x.to_string())
+                .and_then(TimingHistory::try_from),
+        }
+    }
+
+    /// Append the timings of a run. Days without metadata or raw durations can not be keyed and are skipped.
+    pub fn append(&mut self, timings: &Timings) {
+        for timing in &timings.data {
+            let Some(metadata) = &timing.metadata else {
+                continue;
+            };
+
+            if timing.parts.is_empty() {
+                continue;
+            }
+
+            let entry = HistoryEntry {
+                day: timing.day,
+                metadata: metadata.clone(),
+                parts: timing.parts.clone(),
+            };
+
+            self.data.retain(|e| !e.is_same_run(&entry));
+            self.data.push(entry);
+        }
+
+        self.data
+            .sort_by_key(|entry| (entry.day, entry.metadata.timestamp));
+    }
+
+    fn days(&self, day: Option<Day>) -> Vec<Day> {
+        let mut days: Vec<Day> = self
+            .data
+            .iter()
+            .map(|entry| entry.day)
+            .filter(|d| day.is_none_or(|day| day == *d))
+            .collect();
+        days.dedup();
+        days
+    }
+
+    fn runs(&self, day: Day) -> Vec<&HistoryEntry> {
+        self.data.iter().filter(|entry| entry.day == day).collect()
+    }
+
+    /// Print a table of the runs of every day, with the change to the previous run and a sparkline per part.
+    pub fn print_report(&self, day: Option<Day>) {
+        let days = self.days(day);
+
+        if days.is_empty() {
+            println!("No benchmark history yet, it is recorded by `cargo time --store`.");
+            return;
+        }
+
+        for day in days {
+            let runs = self.runs(day);
+            let parts: Vec<u8> = (0..=2)
+                .filter(|part| runs.iter().any(|run| run.nanos(*part).is_some()))
+                .collect();
+
+            println!("\n{ANSI_BOLD}Day {day}{ANSI_RESET}");
+
+            let mut header = format!("{:<10}  {:<14}", "Date", "Commit");
+            for part in &parts {
+                let name = match part {
+                    0 => "Parse".into(),
+                    part => format!("Part {part}"),
+                };
+                header.push_str(&format!("  {name:<18}"));
+            }
+            println!("{}", header.trim_end());
+
+            for (i, run) in runs.iter().enumerate() {
+                let commit = run.metadata.commit.as_deref().unwrap_or("unknown");
+                let mut row = format!("{:<10}  {commit:<14}", run.metadata.date());
+
+                for part in &parts {
+                    let previous = i.checked_sub(1).and_then(|i| runs[i].nanos(*part));
+                    let cell = match (run.nanos(*part), previous) {
+                        (None, _) => "-".into(),
+                        (Some(nanos), None) => format!("{:.1?}", Duration::from_nanos(nanos)),
+                        (Some(nanos), Some(previous)) => format!(
+                            "{:.1?} ({:+.1}%)",
+                            Duration::from_nanos(nanos),
+                            percent(previous, nanos)
+                        ),
+                    };
+                    row.push_str(&format!("  {cell:<18}"));
+                }
+
+                println!("{}", row.trim_end());
+            }
+
+            let trend: Vec<String> = parts
+                .iter()
+                .map(|part| {
+                    let values: Vec<u64> = runs.iter().filter_map(|run| run.nanos(*part)).collect();
+                    format!("{} {}", step_name(*part), sparkline(&values))
+                })
+                .collect();
+            println!("Trend: {}", trend.join("  "));
+        }
+    }
+
+    /// Export the runs as CSV, with one row per part.
+    pub fn to_csv(&self, day: Option<Day>) -> String {
+        let mut lines = vec!["day,part,date,commit,nanos,samples,rustc,host".to_string()];
+
+        for day in self.days(day) {
+            for run in self.runs(day) {
+                let metadata = &run.metadata;
+                for part in &run.parts {
+                    lines.push(
+                        [
+                            day.to_string(),
+                            part.part.to_string(),
+                            metadata.date(),
+                            csv_field(metadata.commit.as_deref()),
+                            part.nanos.to_string(),
+                            part.samples.map(|s| s.to_string()).unwrap_or_default(),
+                            csv_field(metadata.rustc.as_deref()),
+                            csv_field(metadata.host.as_deref()),
+                        ]
+                        .join(","),
+                    );
+                }
+            }
+        }
+
+        lines.join("\n")
+    }
+}
+
+fn step_name(part: u8) -> String {
+    match part {
+        0 => "parse".into(),
+        part => format!("part {part}"),
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn percent(previous: u64, current: u64) -> f64 {
+    if previous == 0 {
+        return 0.0;
+    }
+    (current as f64 - previous as f64) / previous as f64 * 100.0
+}
+
+/// Draw values as a line of bars, scaled between the smallest and largest value.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn sparkline(values: &[u64]) -> String {
+    let (Some(min), Some(max)) = (values.iter().min(), values.iter().max()) else {
+        return String::new();
+    };
+    let range = (max - min) as f64;
+
+    values
+        .iter()
+        .map(|value| {
+            if range == 0.0 {
+                return SPARKS[0];
+            }
+            let level = ((value - min) as f64 / range * 7.0).round() as usize;
+            SPARKS[level.min(7)]
+        })
+        .collect()
+}
+
+/// Quote a field if it contains a separator or quote.
+fn csv_field(value: Option<&str>) -> String {
+    let value = value.unwrap_or_default();
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<TimingHistory> for JsonValue {
+    fn from(value: TimingHistory) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for TimingHistory {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(TimingHistory {
+            data: json_data
+                .iter()
+                .map(HistoryEntry::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&HistoryEntry> for JsonValue {
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("metadata".into(), JsonValue::from(&value.metadata));
+        map.insert(
+            "parts".into(),
+            JsonValue::Array(value.parts.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| day.parse().ok())
+            .ok_or("Expected entry.day to be a valid day.")?;
+
+        let metadata = BenchMetadata::try_from(
+            json.get("metadata")
+                .ok_or("Expected entry to have key `metadata`.")?,
+        )?;
+
+        let parts = json
+            .get("parts")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected entry.parts to be an array.")?
+            .iter()
+            .map(PartTiming::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(HistoryEntry {
+            day,
+            metadata,
+            parts,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{sparkline, TimingHistory};
+    use crate::{
+        day,
+        template::{
+            bench_metadata::BenchMetadata,
+            timings::{PartTiming, Timing, Timings},
+        },
+    };
+
+    fn timings(timestamp: u64, commit: &str, part_1: u64) -> Timings {
+        Timings {
+            data: vec![Timing {
+                day: day!(1),
+                part_1: Some("1.0ms".into()),
+                part_2: None,
+                total_nanos: 0.0,
+                part_1_stats: None,
+                part_2_stats: None,
+                parse: None,
+                parse_stats: None,
+                failures: vec![],
+                memory: None,
+                parts: vec![PartTiming {
+                    part: 1,
+                    nanos: part_1,
+                    samples: Some(10),
+                }],
+                metadata: Some(BenchMetadata {
+                    timestamp,
+                    commit: Some(commit.into()),
+                    rustc: Some("rustc 1.83.0".into()),
+                    host: Some("builder (AMD Ryzen 7 5800X, 16 threads)".into()),
+                }),
+            }],
+        }
+    }
+
+    #[test]
+    fn keys_runs_by_commit_and_date() {
+        let mut history = TimingHistory::default();
+        history.append(&timings(1_733_029_200, "abc1234", 3_000));
+        // same commit and date, replaces the first run.
+        history.append(&timings(1_733_032_800, "abc1234", 2_000));
+        // same commit on another day, and another commit.
+        history.append(&timings(1_733_115_600, "abc1234", 1_500));
+        history.append(&timings(1_733_119_200, "def5678", 1_000));
+
+        let nanos: Vec<u64> = history.data.iter().map(|e| e.parts[0].nanos).collect();
+        assert_eq!(nanos, vec![2_000, 1_500, 1_000]);
+
+        let json = tinyjson::JsonValue::from(history.clone())
+            .stringify()
+            .unwrap();
+        let parsed = TimingHistory::try_from(json).unwrap();
+        assert_eq!(parsed.data, history.data);
+    }
+
+    #[test]
+    fn exports_csv() {
+        let mut history = TimingHistory::default();
+        history.append(&timings(1_733_029_200, "abc1234", 2_000));
+
+        assert_eq!(
+            history.to_csv(Some(day!(1))),
+            [
+                "day,part,date,commit,nanos,samples,rustc,host",
+                "01,1,2024-12-01,abc1234,2000,10,rustc 1.83.0,\"builder (AMD Ryzen 7 5800X, 16 threads)\"",
+            ]
+            .join("\n")
+        );
+        assert_eq!(
+            history.to_csv(Some(day!(2))),
+            "day,part,date,commit,nanos,samples,rustc,host"
+        );
+    }
+
+    #[test]
+    fn draws_sparklines() {
+        assert_eq!(sparkline(&[]), "");
+        assert_eq!(sparkline(&[5, 5]), "▁▁");
+        assert_eq!(sparkline(&[100, 200, 800, 450]), "▁▂█▅");
+    }
+}
@@ -1,17 +1,114 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    path::PathBuf,
+    str::FromStr,
+    time::Duration,
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{
+    bench_metadata::BenchMetadata, memory::Memory, results::Failure, stats::Stats, Day, Year,
+};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE_NAME: &str = "timings.json";
+
+/// Version of the timings file. Files without a version were stored before it was introduced and are version 1.
+/// Version 2 adds the raw duration of every part and where and when a day was benched.
+pub const TIMINGS_VERSION: u64 = 2;
+
+/// Raw duration of a single part, with the part number `0` standing for the parse step.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PartTiming {
+    pub part: u8,
+    /// Median duration if the part was benched, in nanoseconds.
+    pub nanos: u64,
+    /// Number of runs, unknown for timings migrated from formatted durations.
+    pub samples: Option<u64>,
+}
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
+    /// Formatted median duration of part 1.
     pub part_1: Option<String>,
+    /// Formatted median duration of part 2.
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    pub part_1_stats: Option<Stats>,
+    pub part_2_stats: Option<Stats>,
+    /// Formatted median duration of the optional parse step.
+    pub parse: Option<String>,
+    pub parse_stats: Option<Stats>,
+    /// Parts that panicked, with the part number `0` standing for the parse step.
+    pub failures: Vec<(u8, Failure)>,
+    /// Heap usage of the day: the highest peak and the sum of the allocations of its parse step and parts.
+    pub memory: Option<Memory>,
+    /// Raw durations of the parse step and parts that were benched.
+    pub parts: Vec<PartTiming>,
+    pub metadata: Option<BenchMetadata>,
+}
+
+impl Timing {
+    /// Median duration of a part, or of the parse step for part `0`.
+    /// Timings stored without raw durations fall back to the statistics or the formatted duration.
+    pub fn part_duration(&self, part: u8) -> Option<Duration> {
+        if let Some(timing) = self.parts.iter().find(|p| p.part == part) {
+            return Some(Duration::from_nanos(timing.nanos));
+        }
+
+        let (formatted, stats) = match part {
+            0 => (&self.parse, &self.parse_stats),
+            1 => (&self.part_1, &self.part_1_stats),
+            2 => (&self.part_2, &self.part_2_stats),
+            _ => return None,
+        };
+
+        match stats {
+            Some(stats) => Some(stats.median),
+            None => parse_duration(formatted.as_deref()?),
+        }
+    }
+
+    /// Upgrade a day stored in an older version of the timings file.
+    #[allow(clippy::cast_possible_truncation)]
+    fn migrate(mut self, version: u64) -> Self {
+        if version < 2 {
+            let stats = [self.parse_stats, self.part_1_stats, self.part_2_stats];
+            self.parts = (0..=2)
+                .filter_map(|part| {
+                    Some(PartTiming {
+                        part,
+                        nanos: self.part_duration(part)?.as_nanos() as u64,
+                        samples: stats[usize::from(part)].map(|s| (s.samples + s.outliers) as u64),
+                    })
+                })
+                .collect();
+        }
+
+        self
+    }
+}
+
+/// Parse a duration as formatted by the runner, e.g. `74.1ms` or `39.0ns`.
+pub fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.trim();
+    let split = s.find(|c: char| !(c.is_ascii_digit() || c == '.'))?;
+    let (value, unit) = s.split_at(split);
+    let value: f64 = value.parse().ok()?;
+
+    let nanos = match unit.trim() {
+        "ns" => value,
+        "µs" | "us" => value * 1e3,
+        "ms" => value * 1e6,
+        "s" => value * 1e9,
+        _ => return None,
+    };
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Some(Duration::from_nanos(nanos.round() as u64))
 }
 
 /// Represents benchmark times for a set of days.
@@ -22,26 +119,31 @@ pub struct Timings {
 }
 
 impl Timings {
+    /// Path of the timings file of a year.
+    pub fn file_path(year: Year) -> PathBuf {
+        year.data_dir().join(TIMINGS_FILE_NAME)
+    }
+
     /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(Timings::file_path(year))?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from);
-
-        match s {
-            Ok(timings) => timings,
-            Err(e) => {
-                eprintln!("{e}");
-                Timings::default()
-            }
+    /// Rehydrate timings from a JSON file, migrating files of older versions. If not present, returns empty timings.
+    /// Errors if the file can not be read, e.g. because it was stored by a newer version of the template.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        match fs::read_to_string(Timings::file_path(year)) {
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Timings::default()),
+            s => s.map_err(|x| x.to_string()).and_then(Timings::try_from),
         }
+        .map_err(|e| {
+            format!(
+                "Failed to read \"{}\": {e}",
+                Timings::file_path(year).display()
+            )
+        })
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
@@ -58,7 +160,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -77,9 +179,12 @@ impl Timings {
 /* -------------------------------------------------------------------------- */
 
 impl From<Timings> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert("version".into(), JsonValue::Number(TIMINGS_VERSION as f64));
+
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
@@ -95,9 +200,35 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        let version = match json.get("version") {
+            None => 1,
+            Some(JsonValue::Number(n))
+                if n.fract() == 0.0 && (1.0..=f64::from(u32::MAX)).contains(n) =>
+            {
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let version = *n as u64;
+                version
+            }
+            Some(v) => {
+                return Err(format!(
+                    "unknown timings version `{}`.",
+                    v.stringify().unwrap_or_default()
+                ))
+            }
+        };
+
+        if version > TIMINGS_VERSION {
+            return Err(format!(
+                "timings were stored in version {version}, but this template only reads versions up to {TIMINGS_VERSION}. \
+                Update the template or move the file away."
+            ));
+        }
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
@@ -106,7 +237,7 @@ impl TryFrom<String> for Timings {
         Ok(Timings {
             data: json_data
                 .iter()
-                .map(Timing::try_from)
+                .map(|t| Timing::try_from(t).map(|t| t.migrate(version)))
                 .collect::<Result<_, _>>()?,
         })
     }
@@ -140,6 +271,55 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        // NOTE: statistics are only written when present to keep files of older versions unchanged.
+        if let Some(stats) = &value.part_1_stats {
+            map.insert("part_1_stats".into(), JsonValue::from(stats));
+        }
+
+        if let Some(stats) = &value.part_2_stats {
+            map.insert("part_2_stats".into(), JsonValue::from(stats));
+        }
+
+        if let Some(parse) = &value.parse {
+            map.insert("parse".into(), JsonValue::String(parse.clone()));
+        }
+
+        if let Some(stats) = &value.parse_stats {
+            map.insert("parse_stats".into(), JsonValue::from(stats));
+        }
+
+        if let Some(memory) = &value.memory {
+            map.insert("memory".into(), JsonValue::from(memory));
+        }
+
+        map.insert(
+            "parts".into(),
+            JsonValue::Array(value.parts.iter().map(JsonValue::from).collect()),
+        );
+
+        if let Some(metadata) = &value.metadata {
+            map.insert("metadata".into(), JsonValue::from(metadata));
+        }
+
+        if !value.failures.is_empty() {
+            map.insert(
+                "failures".into(),
+                JsonValue::Array(
+                    value
+                        .failures
+                        .iter()
+                        .map(|(part, failure)| {
+                            let mut json = JsonValue::from(failure);
+                            if let JsonValue::Object(map) = &mut json {
+                                map.insert("part".into(), JsonValue::Number(f64::from(*part)));
+                            }
+                            json
+                        })
+                        .collect(),
+                ),
+            );
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -173,11 +353,118 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: timings stored by older versions have no statistics.
+        let part_1_stats = json.get("part_1_stats").map(Stats::try_from).transpose()?;
+        let part_2_stats = json.get("part_2_stats").map(Stats::try_from).transpose()?;
+
+        // NOTE: days without a parse step have no parse timing.
+        let parse = json
+            .get("parse")
+            .map(|v| {
+                v.get::<String>()
+                    .ok_or("Expected timing.parse to be a string.")
+            })
+            .transpose()?;
+        let parse_stats = json.get("parse_stats").map(Stats::try_from).transpose()?;
+
+        // NOTE: heap usage is not measured with dhat and was not stored by older versions.
+        let memory = json.get("memory").map(Memory::try_from).transpose()?;
+
+        // NOTE: files of version 1 have neither raw durations nor metadata, they are migrated afterwards.
+        let parts = match json.get("parts") {
+            None => vec![],
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .ok_or("Expected timing.parts to be an array.")?
+                .iter()
+                .map(PartTiming::try_from)
+                .collect::<Result<_, _>>()?,
+        };
+        let metadata = json
+            .get("metadata")
+            .map(BenchMetadata::try_from)
+            .transpose()?;
+
+        let failures = match json.get("failures") {
+            None => vec![],
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .ok_or("Expected timing.failures to be an array.")?
+                .iter()
+                .map(|v| {
+                    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                    let part = v
+                        .get::<HashMap<String, JsonValue>>()
+                        .and_then(|f| f.get("part"))
+                        .and_then(|p| p.get::<f64>().copied())
+                        .ok_or("Expected failure.part to be a number.")?
+                        as u8;
+                    Ok((part, Failure::try_from(v)?))
+                })
+                .collect::<Result<_, String>>()?,
+        };
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            part_1_stats,
+            part_2_stats,
+            parse: parse.cloned(),
+            parse_stats,
+            failures,
+            memory,
+            parts,
+            metadata,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartTiming> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &PartTiming) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("nanos".into(), JsonValue::Number(value.nanos as f64));
+        map.insert(
+            "samples".into(),
+            value
+                .samples
+                .map_or(JsonValue::Null, |s| JsonValue::Number(s as f64)),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartTiming {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part timing to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected part.{key} to be a number."))
+        };
+
+        let samples = match json.get("samples") {
+            None | Some(JsonValue::Null) => None,
+            Some(_) => Some(number("samples")? as u64),
+        };
+
+        Ok(PartTiming {
+            part: number("part")? as u8,
+            nanos: number("nanos")? as u64,
+            samples,
         })
     }
 }
@@ -198,18 +485,42 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    failures: vec![],
+                    memory: None,
+                    parts: vec![],
+                    metadata: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    failures: vec![],
+                    memory: None,
+                    parts: vec![],
+                    metadata: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
                     total_nanos: 4e+10,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    failures: vec![],
+                    memory: None,
+                    parts: vec![],
+                    metadata: None,
                 },
             ],
         }
@@ -244,6 +555,54 @@ mod tests {
             Timings::try_from(json).unwrap();
         }
 
+        #[test]
+        fn migrates_version_1() {
+            use crate::template::timings::PartTiming;
+
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1.5ms", "part_2": null, "total_nanos": 1500000,
+                "part_1_stats": { "mean": 1600000, "median": 1500000, "min": 1000000, "max": 2000000,
+                    "stddev": 100, "p95": 1900000, "samples": 90, "outliers": 10 } },
+                { "day": "02", "part_1": "74.1µs", "part_2": "1.0s", "total_nanos": 1000074100 }] }"#
+                .to_string();
+            let timings = Timings::try_from(json).unwrap();
+
+            assert_eq!(
+                timings.data[0].parts,
+                vec![PartTiming {
+                    part: 1,
+                    nanos: 1_500_000,
+                    samples: Some(100),
+                }]
+            );
+            assert_eq!(
+                timings.data[1].parts,
+                vec![
+                    PartTiming {
+                        part: 1,
+                        nanos: 74_100,
+                        samples: None,
+                    },
+                    PartTiming {
+                        part: 2,
+                        nanos: 1_000_000_000,
+                        samples: None,
+                    },
+                ]
+            );
+            assert_eq!(timings.data[1].metadata, None);
+        }
+
+        #[test]
+        fn errors_for_unknown_versions() {
+            let json = r#"{ "version": 3, "data": [] }"#.to_string();
+            let error = Timings::try_from(json).unwrap_err();
+            assert!(error.contains("stored in version 3"));
+
+            let json = r#"{ "version": "2", "data": [] }"#.to_string();
+            let error = Timings::try_from(json).unwrap_err();
+            assert_eq!(error, r#"unknown timings version `"2"`."#);
+        }
+
         #[test]
         #[should_panic]
         fn panics_for_malformed_timings() {
@@ -273,6 +632,72 @@ mod tests {
                 3
             );
         }
+
+        #[test]
+        fn roundtrips_statistics() {
+            use crate::template::stats::{OutlierRule, Stats};
+            use std::time::Duration;
+
+            let mut timings = get_mock_timings();
+            timings.data[0].part_2_stats =
+                Stats::from_samples(&[Duration::from_millis(20)], OutlierRule::default());
+            timings.data[1].parse = Some("1.5µs".into());
+            timings.data[1].failures = vec![(
+                2,
+                crate::template::results::Failure::Panic {
+                    message: "boom".into(),
+                    location: None,
+                },
+            )];
+
+            let json = JsonValue::from(timings).stringify().unwrap();
+            let timings = super::Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].part_1_stats, None);
+            assert_eq!(
+                timings.data[0].part_2_stats.unwrap().median,
+                Duration::from_millis(20)
+            );
+            assert_eq!(timings.data[0].parse, None);
+            assert!(timings.data[0].failures.is_empty());
+            assert_eq!(timings.data[1].failures[0].0, 2);
+            assert_eq!(timings.data[1].failures[0].1.to_string(), "panicked: boom");
+            assert_eq!(
+                timings.data[1].part_duration(0),
+                Some(Duration::from_nanos(1500))
+            );
+        }
+
+        #[test]
+        fn roundtrips_raw_durations_and_metadata() {
+            use crate::template::{bench_metadata::BenchMetadata, timings::PartTiming};
+            use std::time::Duration;
+
+            let mut timings = get_mock_timings();
+            timings.data[0].parts = vec![PartTiming {
+                part: 1,
+                nanos: 10_000_123,
+                samples: Some(99),
+            }];
+            timings.data[0].metadata = Some(BenchMetadata {
+                timestamp: 1_733_029_200,
+                commit: Some("1a409ea-dirty".into()),
+                rustc: Some("rustc 1.83.0".into()),
+                host: None,
+            });
+
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            assert!(json.contains(r#""version":2"#));
+
+            let parsed = super::Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[0].parts, timings.data[0].parts);
+            assert_eq!(parsed.data[0].metadata, timings.data[0].metadata);
+            assert_eq!(
+                parsed.data[0].part_duration(1),
+                Some(Duration::from_nanos(10_000_123))
+            );
+            // files of the current version are not migrated again.
+            assert!(parsed.data[1].parts.is_empty());
+        }
     }
 
     mod is_day_complete {
@@ -289,10 +714,18 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    failures: vec![],
+                    memory: None,
+                    parts: vec![],
+                    metadata: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -303,10 +736,18 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    failures: vec![],
+                    memory: None,
+                    parts: vec![],
+                    metadata: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -317,10 +758,18 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    failures: vec![],
+                    memory: None,
+                    parts: vec![],
+                    metadata: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -341,6 +790,14 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    failures: vec![],
+                    memory: None,
+                    parts: vec![],
+                    metadata: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -361,6 +818,14 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    failures: vec![],
+                    memory: None,
+                    parts: vec![],
+                    metadata: None,
                 }],
             };
             let merged = timings.merge(&other);
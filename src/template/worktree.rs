@@ -0,0 +1,83 @@
+/// Temporary git worktrees, to build and run the solutions of another commit next to the working tree.
+use std::{
+    env,
+    ffi::OsStr,
+    fs, io,
+    path::{Path, PathBuf},
+    process::{self, Command},
+};
+
+/// A commit checked out into a temporary directory. The worktree is removed again when dropped.
+pub struct Worktree {
+    path: PathBuf,
+    /// Short hash of the checked out commit.
+    pub commit: String,
+}
+
+impl Worktree {
+    /// Check out `rev` into a temporary worktree. Errors if `rev` does not name a commit.
+    pub fn checkout(rev: &str) -> Result<Self, String> {
+        let commit = git([
+            "rev-parse",
+            "--verify",
+            "--short",
+            &format!("{rev}^{{commit}}"),
+        ])
+        .map_err(|_| format!("`{rev}` does not name a commit."))?;
+
+        let path = env::temp_dir().join(format!("aoc-worktree-{}-{commit}", process::id()));
+        git([
+            OsStr::new("worktree"),
+            OsStr::new("add"),
+            OsStr::new("--quiet"),
+            OsStr::new("--detach"),
+            path.as_os_str(),
+            OsStr::new(&commit),
+        ])?;
+
+        Ok(Worktree { path, commit })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Copy a file of the working tree to the same relative path in the worktree, e.g. a puzzle input.
+    pub fn copy_file(&self, relative: &Path) -> io::Result<()> {
+        let target = self.path.join(relative);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(relative, target).map(|_| ())
+    }
+}
+
+impl Drop for Worktree {
+    fn drop(&mut self) {
+        let removed = git([
+            OsStr::new("worktree"),
+            OsStr::new("remove"),
+            OsStr::new("--force"),
+            self.path.as_os_str(),
+        ]);
+
+        if removed.is_err() {
+            let _ = fs::remove_dir_all(&self.path);
+            let _ = git(["worktree", "prune"]);
+        }
+    }
+}
+
+/// Run git and return its trimmed output, or its error message if it failed.
+fn git<I: IntoIterator<Item = S>, S: AsRef<OsStr>>(args: I) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
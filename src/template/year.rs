@@ -0,0 +1,118 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// The year configured via `AOC_YEAR` in `.cargo/config.toml`.
+/// Its puzzles use the flat layout (`data/inputs/01.txt`, `src/bin/01.rs`),
+/// every other year is scoped (`data/2023/inputs/01.txt`, `src/bin/2023_01.rs`).
+pub const DEFAULT_YEAR: Option<Year> = match option_env!("AOC_YEAR") {
+    Some(s) => Year::__parse(s.as_bytes()),
+    None => None,
+};
+
+/// A year of advent of code (i.e. an integer from 2015 onwards).
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2023).unwrap();
+/// assert_eq!(year.to_string(), "2023")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if advent of code took place that year,
+    /// returns [`None`] otherwise.
+    pub const fn new(year: u16) -> Option<Self> {
+        if year < 2015 || year > 9999 {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __parse(s: &[u8]) -> Option<Self> {
+        if s.len() != 4 {
+            return None;
+        }
+
+        let mut year = 0;
+        let mut i = 0;
+        while i < s.len() {
+            if !s[i].is_ascii_digit() {
+                return None;
+            }
+            year = year * 10 + (s[i] - b'0') as u16;
+            i += 1;
+        }
+
+        Self::new(year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// Returns `true` if this is the [`DEFAULT_YEAR`], which uses the flat layout.
+    pub fn is_default(self) -> bool {
+        DEFAULT_YEAR == Some(self)
+    }
+
+    /// The directory holding inputs, examples, puzzles and stored results of this year.
+    pub fn data_dir(self) -> PathBuf {
+        if self.is_default() {
+            PathBuf::from("data")
+        } else {
+            PathBuf::from("data").join(self.to_string())
+        }
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year from 2015 onwards")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Year;
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2023".parse::<Year>().unwrap(), Year(2023));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("23".parse::<Year>().is_err());
+        assert_eq!(Year::__parse(b"2016"), Some(Year(2016)));
+        assert_eq!(Year::__parse(b"20x6"), None);
+        assert_eq!(Year::__parse(b"02016"), None);
+    }
+}